@@ -0,0 +1,64 @@
+//! Follows how loud the computer's sound output is, so clicking can wait for
+//! a sound. The meter only starts once something asks for the level.
+
+use std::{
+	collections::VecDeque,
+	sync::{
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		Once,
+	},
+	thread::{self, sleep},
+	time::Duration,
+};
+
+use crate::platform::AudioMeter;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+/// How many samples the level is averaged over, short enough to catch a beat.
+const WINDOW: usize = 5;
+/// How long to wait before looking for an output device again.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+static START: Once = Once::new();
+static AVAILABLE: AtomicBool = AtomicBool::new(false);
+/// The bits of the current level as an `f32`
+static LEVEL: AtomicU32 = AtomicU32::new(0);
+
+/// The recent output level from 0 to 1, or nothing without an output device.
+pub fn level() -> Option<f32> {
+	START.call_once(spawn);
+
+	AVAILABLE
+		.load(Ordering::Relaxed)
+		.then(|| f32::from_bits(LEVEL.load(Ordering::Relaxed)))
+}
+
+fn spawn() {
+	thread::spawn(|| {
+		let mut samples = VecDeque::with_capacity(WINDOW + 1);
+
+		loop {
+			let Some(meter) = AudioMeter::new() else {
+				sleep(RETRY_INTERVAL);
+				continue;
+			};
+
+			// The default device can change, a failed read means it has to be opened again
+			while let Some(peak) = meter.peak() {
+				samples.push_back(peak);
+				if samples.len() > WINDOW {
+					samples.pop_front();
+				}
+
+				let rms = (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt();
+				LEVEL.store(rms.to_bits(), Ordering::Relaxed);
+				AVAILABLE.store(true, Ordering::Relaxed);
+
+				sleep(SAMPLE_INTERVAL);
+			}
+
+			AVAILABLE.store(false, Ordering::Relaxed);
+			samples.clear();
+		}
+	});
+}
@@ -0,0 +1,127 @@
+//! The different ways clicks can be injected. Some games only react to input
+//! coming in at a certain level, so the backend can be picked in the settings.
+//!
+//! Both backends support the left, right and middle mouse buttons and the
+//! vertical scroll wheel.
+
+use mouse_rs::Mouse;
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter};
+
+use crate::MouseButton;
+
+pub trait ClickBackend {
+	fn press(&self, button: MouseButton) -> Result<(), String>;
+	fn release(&self, button: MouseButton) -> Result<(), String>;
+
+	fn click(&self, button: MouseButton) -> Result<(), String> {
+		self.press(button)?;
+		self.release(button)
+	}
+
+	/// Turns the wheel by `notches`, up if positive and down if negative.
+	fn scroll(&self, notches: i32) -> Result<(), String>;
+}
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum ClickBackendKind {
+	#[strum(serialize = "mouse_rs")]
+	MouseRs,
+	#[strum(serialize = "SendInput")]
+	SendInput,
+}
+
+impl ClickBackend for Mouse {
+	fn press(&self, button: MouseButton) -> Result<(), String> {
+		Mouse::press(self, &button.key()).map_err(|e| e.to_string())
+	}
+
+	fn release(&self, button: MouseButton) -> Result<(), String> {
+		Mouse::release(self, &button.key()).map_err(|e| e.to_string())
+	}
+
+	fn click(&self, button: MouseButton) -> Result<(), String> {
+		Mouse::click(self, &button.key()).map_err(|e| e.to_string())
+	}
+
+	fn scroll(&self, notches: i32) -> Result<(), String> {
+		Mouse::wheel(self, notches).map_err(|e| e.to_string())
+	}
+}
+
+/// Sends raw mouse events through the Windows `SendInput` API.
+pub struct SendInputBackend;
+
+#[cfg(windows)]
+impl SendInputBackend {
+	fn send(&self, flags: u32, data: u32) -> Result<(), String> {
+		use std::mem;
+		use winapi::um::winuser::{SendInput, INPUT, INPUT_MOUSE, MOUSEINPUT};
+
+		unsafe {
+			let mut input: INPUT = mem::zeroed();
+			input.type_ = INPUT_MOUSE;
+			*input.u.mi_mut() = MOUSEINPUT {
+				dx: 0,
+				dy: 0,
+				mouseData: data,
+				dwFlags: flags,
+				time: 0,
+				dwExtraInfo: 0,
+			};
+
+			if SendInput(1, &mut input, mem::size_of::<INPUT>() as i32) != 1 {
+				return Err(std::io::Error::last_os_error().to_string());
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(windows)]
+impl ClickBackend for SendInputBackend {
+	fn press(&self, button: MouseButton) -> Result<(), String> {
+		use winapi::um::winuser::{MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_RIGHTDOWN};
+
+		let flags = match button {
+			MouseButton::Left => MOUSEEVENTF_LEFTDOWN,
+			MouseButton::Right => MOUSEEVENTF_RIGHTDOWN,
+			MouseButton::Middle => MOUSEEVENTF_MIDDLEDOWN,
+		};
+		self.send(flags, 0)
+	}
+
+	fn release(&self, button: MouseButton) -> Result<(), String> {
+		use winapi::um::winuser::{MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_RIGHTUP};
+
+		let flags = match button {
+			MouseButton::Left => MOUSEEVENTF_LEFTUP,
+			MouseButton::Right => MOUSEEVENTF_RIGHTUP,
+			MouseButton::Middle => MOUSEEVENTF_MIDDLEUP,
+		};
+		self.send(flags, 0)
+	}
+
+	fn scroll(&self, notches: i32) -> Result<(), String> {
+		use winapi::um::winuser::{MOUSEEVENTF_WHEEL, WHEEL_DELTA};
+
+		// The wheel amount is a signed value sent in an unsigned field
+		self.send(MOUSEEVENTF_WHEEL, (notches * WHEEL_DELTA as i32) as u32)
+	}
+}
+
+#[cfg(not(windows))]
+impl ClickBackend for SendInputBackend {
+	fn press(&self, _button: MouseButton) -> Result<(), String> {
+		Err("SendInput is only available on Windows".to_owned())
+	}
+
+	fn release(&self, _button: MouseButton) -> Result<(), String> {
+		Err("SendInput is only available on Windows".to_owned())
+	}
+
+	fn scroll(&self, _notches: i32) -> Result<(), String> {
+		Err("SendInput is only available on Windows".to_owned())
+	}
+}
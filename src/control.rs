@@ -0,0 +1,160 @@
+//! A small HTTP server on localhost so scripts can start, stop and check the
+//! selected clicker and set its interval. It only listens while the selected
+//! clicker has Control Server turned on.
+//!
+//! - `GET /status`
+//! - `POST /start`, `POST /stop` and `POST /toggle`
+//! - `POST /interval?seconds=0.1` or `POST /interval?cps=10`
+
+use std::{
+	io::{Read, Write},
+	net::{Ipv4Addr, TcpListener, TcpStream},
+	sync::{Arc, Mutex},
+	thread::{self, sleep},
+	time::Duration,
+};
+
+use serde_json::json;
+
+use crate::{Clickers, IntervalMode, MAX_INTERVAL, SAFE_MODE_MIN_INTERVAL};
+
+/// How often the setting and new connections are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long a client gets to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// Watches the setting on its own thread and listens while it's on.
+pub fn spawn(clickers: Arc<Mutex<Clickers>>) {
+	thread::spawn(move || {
+		let mut listener: Option<(u16, TcpListener)> = None;
+
+		loop {
+			let wanted = {
+				let clickers = clickers.lock().unwrap();
+				let app = clickers.selected().lock().unwrap();
+				app.settings.control_server_enabled.then_some(app.settings.control_server_port)
+			};
+
+			if listener.as_ref().map(|(port, _)| *port) != wanted {
+				listener = wanted.and_then(|port| {
+					// Only reachable from this computer
+					let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok()?;
+					listener.set_nonblocking(true).ok()?;
+					Some((port, listener))
+				});
+			}
+
+			if let Some((_, listener)) = &listener {
+				// Stops at WouldBlock once every waiting connection is handled
+				while let Ok((stream, _)) = listener.accept() {
+					handle_connection(stream, &clickers);
+				}
+			}
+
+			sleep(POLL_INTERVAL);
+		}
+	});
+}
+
+fn handle_connection(mut stream: TcpStream, clickers: &Mutex<Clickers>) {
+	if stream.set_nonblocking(false).is_err() || stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+		return;
+	}
+	let Some(request) = read_request(&mut stream) else {
+		return;
+	};
+
+	let (status, body) = respond(&request, clickers);
+	let response = format!(
+		"HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+		status,
+		body.len(),
+		body,
+	);
+	let _ = stream.write_all(response.as_bytes());
+}
+
+/// Reads until the end of the headers, the body isn't used.
+fn read_request(stream: &mut TcpStream) -> Option<String> {
+	let mut request = Vec::new();
+	let mut buffer = [0; 1024];
+
+	while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+		let read = stream.read(&mut buffer).ok()?;
+		if read == 0 || request.len() + read > MAX_REQUEST_SIZE {
+			return None;
+		}
+		request.extend_from_slice(&buffer[..read]);
+	}
+
+	String::from_utf8(request).ok()
+}
+
+fn respond(request: &str, clickers: &Mutex<Clickers>) -> (&'static str, String) {
+	let mut lines = request.lines();
+	let mut parts = lines.next().unwrap_or_default().split_whitespace();
+	let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+		return error("400 Bad Request", "Malformed request");
+	};
+
+	// Browsers send an Origin with requests from web pages, which shouldn't be able to control it
+	if lines.any(|line| line.to_ascii_lowercase().starts_with("origin:")) {
+		return error("403 Forbidden", "Requests from web pages aren't allowed");
+	}
+
+	let (path, query) = target.split_once('?').unwrap_or((target, ""));
+	let expected_method = if path == "/status" { "GET" } else { "POST" };
+	if !matches!(path, "/status" | "/start" | "/stop" | "/toggle" | "/interval") {
+		return error("404 Not Found", "Unknown command");
+	}
+	if method != expected_method {
+		return error("405 Method Not Allowed", &format!("Use {} for {}", expected_method, path));
+	}
+
+	let clickers = clickers.lock().unwrap();
+	let mut app = clickers.selected().lock().unwrap();
+
+	match path {
+		"/start" if !app.clicker_enabled => app.toggle_clicker(),
+		"/stop" => app.clicker_enabled = false,
+		"/toggle" => app.toggle_clicker(),
+		"/interval" => {
+			let value = |name: &str| {
+				query
+					.split('&')
+					.filter_map(|pair| pair.split_once('='))
+					.find(|(key, _)| *key == name)
+					.and_then(|(_, value)| value.parse::<f64>().ok())
+			};
+			let seconds = match (value("seconds"), value("cps")) {
+				(Some(seconds), None) => seconds,
+				(None, Some(cps)) if cps > 0.0 => 1.0 / cps,
+				_ => return error("400 Bad Request", "Pass either seconds or a positive cps"),
+			};
+
+			let min = if app.settings.safe_mode { SAFE_MODE_MIN_INTERVAL } else { 0.0 };
+			if !(min..=MAX_INTERVAL).contains(&seconds) {
+				return error("400 Bad Request", "Interval is out of range");
+			}
+			app.settings.interval_mode = IntervalMode::Constant;
+			app.settings.set_constant_interval(seconds);
+		}
+		_ => {}
+	}
+
+	let status = json!({
+		"name": app.name,
+		"running": app.clicker_enabled,
+		"armed": app.armed,
+		"session_clicks": app.session_clicks,
+		"total_clicks": app.total_clicks,
+		"interval_mode": app.settings.interval_mode.as_ref(),
+		"interval": app.settings.constant_interval(),
+	});
+	("200 OK", status.to_string())
+}
+
+fn error(status: &'static str, message: &str) -> (&'static str, String) {
+	(status, json!({ "error": message }).to_string())
+}
@@ -0,0 +1,83 @@
+//! Cleans up after a panic before the process goes away: lets go of any mouse
+//! button a clicker is holding and, if the user opted in, saves the tabs for
+//! the next start and writes a crash log to the config directory.
+
+use std::{
+	backtrace::Backtrace,
+	fs,
+	panic::{self, PanicHookInfo},
+	sync::{Arc, Mutex, MutexGuard, TryLockError},
+};
+
+use crate::{platform, settings::SavedTabs, Clickers};
+
+/// Installs the hook in front of the default one, which still prints the panic.
+pub fn install(clickers: Arc<Mutex<Clickers>>) {
+	let default_hook = panic::take_hook();
+	panic::set_hook(Box::new(move |info| {
+		handle_panic(&clickers, info);
+		default_hook(info);
+	}));
+}
+
+/// Locks without waiting, since the panicking thread may be the one holding
+/// the lock and would never let go of it.
+fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+	match mutex.try_lock() {
+		Ok(guard) => Some(guard),
+		Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+		Err(TryLockError::WouldBlock) => None,
+	}
+}
+
+fn handle_panic(clickers: &Mutex<Clickers>, info: &PanicHookInfo) {
+	let Some(clickers) = try_lock(clickers) else {
+		return;
+	};
+
+	let mut saved = SavedTabs {
+		selected: clickers.selected,
+		tabs: Vec::new(),
+	};
+	// The selected tab's choice, or any other's when the selected one can't be read
+	let mut selected_saves = None;
+	let mut any_saves = false;
+	// A tab still locked by the panicking thread can't be read, and saving
+	// without it would lose it for good
+	let mut every_tab = true;
+	for (i, app) in clickers.list.iter().enumerate() {
+		let Some(mut app) = try_lock(app) else {
+			every_tab = false;
+			continue;
+		};
+		app.clicker_enabled = false;
+		app.cancel_key_burst();
+		app.try_release_mouse();
+		if i == clickers.selected {
+			selected_saves = Some(app.settings.save_on_crash);
+		}
+		any_saves |= app.settings.save_on_crash;
+		saved.tabs.push(app.settings.clone());
+	}
+	drop(clickers);
+
+	let save = selected_saves.unwrap_or(any_saves);
+	let (true, Some(dir)) = (save, platform::config_dir()) else {
+		return;
+	};
+
+	// Nothing is left to report a failure to, so these are best effort. The
+	// crash log is still written when the tabs can't all be saved.
+	if every_tab {
+		let _ = saved.save_for_next_run();
+	} else {
+		let _ = fs::create_dir_all(&dir);
+	}
+	let log = format!(
+		"Clickr {} crashed\n\n{}\n\n{}\n",
+		env!("CARGO_PKG_VERSION"),
+		info,
+		Backtrace::force_capture(),
+	);
+	let _ = fs::write(dir.join("crash.log"), log);
+}
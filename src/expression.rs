@@ -0,0 +1,149 @@
+//! A tiny calculator for typing numbers as arithmetic, like `1000/15`.
+//! Supports `+`, `-`, `*`, `/`, parentheses and decimals.
+
+/// How deep parentheses and minus signs can nest. Every level is a recursive
+/// call, so pasting thousands of them would otherwise run out of stack.
+const MAX_DEPTH: usize = 64;
+
+/// Evaluates `text` as an arithmetic expression.
+pub fn evaluate(text: &str) -> Result<f64, String> {
+	let mut parser = Parser {
+		chars: text.chars().filter(|c| !c.is_whitespace()).collect(),
+		position: 0,
+		depth: 0,
+	};
+	let value = parser.sum()?;
+	if parser.position < parser.chars.len() {
+		return Err(format!("Unexpected '{}'", parser.chars[parser.position]));
+	}
+	if !value.is_finite() {
+		return Err("The result isn't a number".to_owned());
+	}
+
+	Ok(value)
+}
+
+struct Parser {
+	chars: Vec<char>,
+	position: usize,
+	depth: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.position).copied()
+	}
+
+	/// Terms added or subtracted, lowest precedence.
+	fn sum(&mut self) -> Result<f64, String> {
+		let mut value = self.product()?;
+		while let Some(op @ ('+' | '-')) = self.peek() {
+			self.position += 1;
+			let rhs = self.product()?;
+			value = if op == '+' { value + rhs } else { value - rhs };
+		}
+		Ok(value)
+	}
+
+	fn product(&mut self) -> Result<f64, String> {
+		let mut value = self.factor()?;
+		while let Some(op @ ('*' | '/')) = self.peek() {
+			self.position += 1;
+			let rhs = self.factor()?;
+			value = if op == '*' { value * rhs } else { value / rhs };
+		}
+		Ok(value)
+	}
+
+	/// A number, a negated factor or a parenthesized sum.
+	fn factor(&mut self) -> Result<f64, String> {
+		match self.peek() {
+			Some('-') => {
+				self.position += 1;
+				Ok(-self.nested(Parser::factor)?)
+			}
+			Some('(') => {
+				self.position += 1;
+				let value = self.nested(Parser::sum)?;
+				if self.peek() != Some(')') {
+					return Err("Missing ')'".to_owned());
+				}
+				self.position += 1;
+				Ok(value)
+			}
+			Some(c) if c.is_ascii_digit() || c == '.' => {
+				let start = self.position;
+				while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+					self.position += 1;
+				}
+				let number: String = self.chars[start..self.position].iter().collect();
+				number.parse().map_err(|_| format!("'{}' isn't a number", number))
+			}
+			Some(c) => Err(format!("Unexpected '{}'", c)),
+			None => Err("Expected a number".to_owned()),
+		}
+	}
+
+	/// Parses one level deeper, up to `MAX_DEPTH`.
+	fn nested(&mut self, parse: fn(&mut Parser) -> Result<f64, String>) -> Result<f64, String> {
+		if self.depth == MAX_DEPTH {
+			return Err("Too many nested parentheses or minus signs".to_owned());
+		}
+		self.depth += 1;
+		let value = parse(self);
+		self.depth -= 1;
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn multiplication_comes_before_addition() {
+		assert_eq!(evaluate("1+2*3"), Ok(7.0));
+		assert_eq!(evaluate("8-4/2"), Ok(6.0));
+		assert_eq!(evaluate("1 + 2 * 3"), Ok(7.0));
+	}
+
+	#[test]
+	fn parentheses_come_first() {
+		assert_eq!(evaluate("(1+2)*3"), Ok(9.0));
+		assert_eq!(evaluate("((2))"), Ok(2.0));
+	}
+
+	#[test]
+	fn minus_negates() {
+		assert_eq!(evaluate("-2"), Ok(-2.0));
+		assert_eq!(evaluate("3*-2"), Ok(-6.0));
+		assert_eq!(evaluate("--2"), Ok(2.0));
+		assert_eq!(evaluate("-(1+1)"), Ok(-2.0));
+	}
+
+	#[test]
+	fn decimals() {
+		assert_eq!(evaluate("1.5*2"), Ok(3.0));
+		assert_eq!(evaluate(".5"), Ok(0.5));
+	}
+
+	#[test]
+	fn invalid_expressions_are_errors() {
+		assert!(evaluate("1/0").is_err());
+		assert!(evaluate("(1").is_err());
+		assert!(evaluate("1..2").is_err());
+		assert!(evaluate("1+2x").is_err());
+		assert!(evaluate("1)").is_err());
+		assert!(evaluate("").is_err());
+	}
+
+	#[test]
+	fn deep_nesting_is_an_error_instead_of_a_crash() {
+		let depth = MAX_DEPTH * 1000;
+		assert!(evaluate(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))).is_err());
+		assert!(evaluate(&format!("{}1", "-".repeat(depth))).is_err());
+
+		let depth = MAX_DEPTH;
+		assert_eq!(evaluate(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))), Ok(1.0));
+	}
+}
@@ -0,0 +1,162 @@
+//! Starting and stopping the clicker with a game controller button.
+
+use std::{
+	sync::{Arc, Mutex},
+	thread,
+	time::{Duration, Instant},
+};
+
+use gilrs::{EventType, Gilrs};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter};
+
+use crate::Clickers;
+
+/// How long to wait for controller events before checking again, so the
+/// thread notices when controllers are plugged in.
+const POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum GamepadButton {
+	#[strum(serialize = "A / Cross")]
+	South,
+	#[strum(serialize = "B / Circle")]
+	East,
+	#[strum(serialize = "X / Square")]
+	West,
+	#[strum(serialize = "Y / Triangle")]
+	North,
+	#[strum(serialize = "Left Bumper")]
+	LeftBumper,
+	#[strum(serialize = "Right Bumper")]
+	RightBumper,
+	#[strum(serialize = "Left Trigger")]
+	LeftTrigger,
+	#[strum(serialize = "Right Trigger")]
+	RightTrigger,
+	Select,
+	Start,
+	#[strum(serialize = "D-Pad Up")]
+	DPadUp,
+	#[strum(serialize = "D-Pad Down")]
+	DPadDown,
+	#[strum(serialize = "D-Pad Left")]
+	DPadLeft,
+	#[strum(serialize = "D-Pad Right")]
+	DPadRight,
+}
+
+impl GamepadButton {
+	fn button(&self) -> gilrs::Button {
+		match self {
+			GamepadButton::South => gilrs::Button::South,
+			GamepadButton::East => gilrs::Button::East,
+			GamepadButton::West => gilrs::Button::West,
+			GamepadButton::North => gilrs::Button::North,
+			GamepadButton::LeftBumper => gilrs::Button::LeftTrigger,
+			GamepadButton::RightBumper => gilrs::Button::RightTrigger,
+			GamepadButton::LeftTrigger => gilrs::Button::LeftTrigger2,
+			GamepadButton::RightTrigger => gilrs::Button::RightTrigger2,
+			GamepadButton::Select => gilrs::Button::Select,
+			GamepadButton::Start => gilrs::Button::Start,
+			GamepadButton::DPadUp => gilrs::Button::DPadUp,
+			GamepadButton::DPadDown => gilrs::Button::DPadDown,
+			GamepadButton::DPadLeft => gilrs::Button::DPadLeft,
+			GamepadButton::DPadRight => gilrs::Button::DPadRight,
+		}
+	}
+}
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum GamepadMode {
+	/// Each press starts or stops the clicker
+	Toggle,
+	/// The clicker runs while the button is held
+	Hold,
+}
+
+/// What to do once when the button is let go in Hold mode, before the clicker stops.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum ReleaseAction {
+	Nothing,
+	/// One last click with the selected mouse button
+	#[strum(serialize = "One Click")]
+	Click,
+	/// Lets go of the selected mouse button in case it's still held
+	#[strum(serialize = "Release Button")]
+	Release,
+}
+
+/// Listens for controller buttons on its own thread and starts or stops the
+/// selected clicker like the toggle hotkey does. Does nothing if no
+/// controller backend is available.
+///
+/// With a Hold Delay, holding the button only starts the clicker once it's
+/// been held that long, and a shorter tap clicks once instead, like a key
+/// that only starts repeating when held.
+pub fn spawn(clickers: Arc<Mutex<Clickers>>) {
+	thread::spawn(move || {
+		let Ok(mut gilrs) = Gilrs::new() else {
+			return;
+		};
+		// When the Hold button went down, until the delay has passed
+		let mut held_since: Option<(Instant, Duration)> = None;
+
+		loop {
+			// Wakes up in time to start the clicker once the delay is over
+			let timeout = held_since.map_or(POLL_TIMEOUT, |(since, delay)| {
+				delay.saturating_sub(since.elapsed()).min(POLL_TIMEOUT)
+			});
+			let event = gilrs.next_event_blocking(Some(timeout));
+
+			if held_since.is_some_and(|(since, delay)| since.elapsed() >= delay) {
+				held_since = None;
+				let clickers = clickers.lock().unwrap();
+				let mut app = clickers.selected().lock().unwrap();
+				if !app.clicker_enabled {
+					app.toggle_clicker();
+				}
+			}
+
+			let Some(event) = event else {
+				continue;
+			};
+			let (button, pressed) = match event.event {
+				EventType::ButtonPressed(button, _) => (button, true),
+				EventType::ButtonReleased(button, _) => (button, false),
+				_ => continue,
+			};
+
+			let clickers = clickers.lock().unwrap();
+			let mut app = clickers.selected().lock().unwrap();
+			if !app.settings.gamepad_enabled || app.settings.gamepad_button.button() != button {
+				continue;
+			}
+			app.record_user_input();
+
+			match (app.settings.gamepad_mode, pressed) {
+				(GamepadMode::Toggle, true) => app.toggle_clicker(),
+				(GamepadMode::Hold, true) => {
+					let delay = Duration::from_millis(app.settings.hold_activation_delay_ms as u64);
+					if !delay.is_zero() {
+						held_since = Some((Instant::now(), delay));
+					} else if !app.clicker_enabled {
+						app.toggle_clicker();
+					}
+				}
+				(GamepadMode::Hold, false) => {
+					if held_since.take().is_some() {
+						// Let go before the delay, so it was a tap
+						app.tap_click();
+					} else {
+						if app.clicker_enabled {
+							app.perform_release_action();
+						}
+						app.clicker_enabled = false;
+					}
+				}
+				(GamepadMode::Toggle, false) => {}
+			}
+		}
+	});
+}
@@ -0,0 +1,339 @@
+//! Global hotkeys. Keys are stored as `egui::Key` so they can be captured in
+//! the UI and saved, and are matched against the `inputbot` hook events.
+
+use eframe::egui::{Key, Modifiers};
+use inputbot::KeybdKey;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, EnumIter};
+
+/// The keys the clicker presses itself, which a hotkey can't use without
+/// triggering while clicking. It only sends mouse input so far.
+pub const SENT_KEYS: &[Hotkey] = &[];
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug)]
+pub enum HotkeyAction {
+	#[strum(serialize = "Toggle Clicker")]
+	Toggle,
+	#[strum(serialize = "Start Clicker")]
+	Start,
+	#[strum(serialize = "Stop Clicker")]
+	Stop,
+	/// Stops every clicker, not just the selected one
+	#[strum(serialize = "Stop All Clickers")]
+	StopAll,
+	#[strum(serialize = "Compact Mode")]
+	CompactMode,
+	/// Clicks a set number of times once, without starting the clicker
+	#[strum(serialize = "Click Burst")]
+	Burst,
+	/// Turns Color Mode on or off, only while stopped
+	#[strum(serialize = "Color Mode")]
+	ColorMode,
+	/// Halves the interval, works while clicking
+	#[strum(serialize = "Double Speed")]
+	SpeedUp,
+	/// Doubles the interval, works while clicking
+	#[strum(serialize = "Halve Speed")]
+	SlowDown,
+}
+
+impl HotkeyAction {
+	/// Whether the action only starts or stops its own clicker, so it can run
+	/// from a tab that isn't selected with Background Hotkeys on.
+	pub fn per_clicker(&self) -> bool {
+		matches!(self, HotkeyAction::Toggle | HotkeyAction::Start | HotkeyAction::Stop)
+	}
+}
+
+/// A key and the modifiers that have to be held with it, like Ctrl+F6. It
+/// only matches with exactly these modifiers held.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(from = "StoredHotkey")]
+pub struct Hotkey {
+	pub key: Key,
+	pub ctrl: bool,
+	pub shift: bool,
+	pub alt: bool,
+}
+
+impl Hotkey {
+	pub fn new(key: Key, modifiers: Modifiers) -> Hotkey {
+		Hotkey {
+			key,
+			ctrl: modifiers.ctrl,
+			shift: modifiers.shift,
+			alt: modifiers.alt,
+		}
+	}
+
+	pub fn name(&self) -> String {
+		let mut name = String::new();
+		for (held, modifier) in [(self.ctrl, "Ctrl+"), (self.shift, "Shift+"), (self.alt, "Alt+")] {
+			if held {
+				name += modifier;
+			}
+		}
+
+		name + self.key.name()
+	}
+}
+
+impl From<Key> for Hotkey {
+	fn from(key: Key) -> Hotkey {
+		Hotkey::new(key, Modifiers::NONE)
+	}
+}
+
+/// Hotkeys used to be saved as just the key, which still loads.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredHotkey {
+	Key(Key),
+	Hotkey {
+		key: Key,
+		#[serde(default)]
+		ctrl: bool,
+		#[serde(default)]
+		shift: bool,
+		#[serde(default)]
+		alt: bool,
+	},
+}
+
+impl From<StoredHotkey> for Hotkey {
+	fn from(stored: StoredHotkey) -> Hotkey {
+		match stored {
+			StoredHotkey::Key(key) => key.into(),
+			StoredHotkey::Hotkey { key, ctrl, shift, alt } => Hotkey { key, ctrl, shift, alt },
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Keybinds {
+	pub toggle: Option<Hotkey>,
+	pub start: Option<Hotkey>,
+	pub stop: Option<Hotkey>,
+	pub stop_all: Option<Hotkey>,
+	pub compact_mode: Option<Hotkey>,
+	pub burst: Option<Hotkey>,
+	pub color_mode: Option<Hotkey>,
+	pub speed_up: Option<Hotkey>,
+	pub slow_down: Option<Hotkey>,
+	/// Whether this clicker's own start and stop keys work while another tab is selected
+	pub background: bool,
+}
+
+impl Default for Keybinds {
+	fn default() -> Self {
+		Keybinds {
+			toggle: Some(Key::F6.into()),
+			start: None,
+			stop: None,
+			stop_all: None,
+			compact_mode: None,
+			burst: None,
+			color_mode: None,
+			speed_up: None,
+			slow_down: None,
+			background: false,
+		}
+	}
+}
+
+impl Keybinds {
+	pub fn get(&self, action: HotkeyAction) -> Option<Hotkey> {
+		match action {
+			HotkeyAction::Toggle => self.toggle,
+			HotkeyAction::Start => self.start,
+			HotkeyAction::Stop => self.stop,
+			HotkeyAction::StopAll => self.stop_all,
+			HotkeyAction::CompactMode => self.compact_mode,
+			HotkeyAction::Burst => self.burst,
+			HotkeyAction::ColorMode => self.color_mode,
+			HotkeyAction::SpeedUp => self.speed_up,
+			HotkeyAction::SlowDown => self.slow_down,
+		}
+	}
+
+	pub fn get_mut(&mut self, action: HotkeyAction) -> &mut Option<Hotkey> {
+		match action {
+			HotkeyAction::Toggle => &mut self.toggle,
+			HotkeyAction::Start => &mut self.start,
+			HotkeyAction::Stop => &mut self.stop,
+			HotkeyAction::StopAll => &mut self.stop_all,
+			HotkeyAction::CompactMode => &mut self.compact_mode,
+			HotkeyAction::Burst => &mut self.burst,
+			HotkeyAction::ColorMode => &mut self.color_mode,
+			HotkeyAction::SpeedUp => &mut self.speed_up,
+			HotkeyAction::SlowDown => &mut self.slow_down,
+		}
+	}
+
+	/// The action bound to `hotkey`, if any.
+	pub fn action_for(&self, hotkey: Hotkey) -> Option<HotkeyAction> {
+		HotkeyAction::iter().find(|action| self.get(*action) == Some(hotkey))
+	}
+
+	/// Every action that shares its hotkey with another action, or with one of
+	/// the keys the clicker presses itself, which would trigger it while clicking.
+	pub fn conflicts(&self, sent_keys: &[Hotkey]) -> Vec<HotkeyAction> {
+		HotkeyAction::iter()
+			.filter(|action| {
+				self.get(*action).is_some_and(|key| {
+					sent_keys.contains(&key)
+						|| HotkeyAction::iter().any(|other| other != *action && self.get(other) == Some(key))
+				})
+			})
+			.collect()
+	}
+}
+
+/// The hotkey for a key reported by the global hook, with the modifiers held
+/// right now.
+pub fn hook_hotkey(key: KeybdKey) -> Option<Hotkey> {
+	use KeybdKey::*;
+
+	let held = |left: KeybdKey, right: KeybdKey| left.is_pressed() || right.is_pressed();
+	Some(Hotkey {
+		key: egui_key(key)?,
+		ctrl: held(LControlKey, RControlKey),
+		shift: held(LShiftKey, RShiftKey),
+		alt: held(LAltKey, RAltKey),
+	})
+}
+
+/// The `egui` key for a key reported by the global hook. Keys `egui` has no
+/// equivalent for, like modifiers and media keys, can't be used as hotkeys.
+fn egui_key(key: KeybdKey) -> Option<Key> {
+	use KeybdKey::*;
+
+	Some(match key {
+		BackspaceKey => Key::Backspace,
+		TabKey => Key::Tab,
+		EnterKey => Key::Enter,
+		EscapeKey => Key::Escape,
+		SpaceKey => Key::Space,
+		PageUpKey => Key::PageUp,
+		PageDownKey => Key::PageDown,
+		EndKey => Key::End,
+		HomeKey => Key::Home,
+		LeftKey => Key::ArrowLeft,
+		UpKey => Key::ArrowUp,
+		RightKey => Key::ArrowRight,
+		DownKey => Key::ArrowDown,
+		InsertKey => Key::Insert,
+		DeleteKey => Key::Delete,
+		Numrow0Key | Numpad0Key => Key::Num0,
+		Numrow1Key | Numpad1Key => Key::Num1,
+		Numrow2Key | Numpad2Key => Key::Num2,
+		Numrow3Key | Numpad3Key => Key::Num3,
+		Numrow4Key | Numpad4Key => Key::Num4,
+		Numrow5Key | Numpad5Key => Key::Num5,
+		Numrow6Key | Numpad6Key => Key::Num6,
+		Numrow7Key | Numpad7Key => Key::Num7,
+		Numrow8Key | Numpad8Key => Key::Num8,
+		Numrow9Key | Numpad9Key => Key::Num9,
+		AKey => Key::A,
+		BKey => Key::B,
+		CKey => Key::C,
+		DKey => Key::D,
+		EKey => Key::E,
+		FKey => Key::F,
+		GKey => Key::G,
+		HKey => Key::H,
+		IKey => Key::I,
+		JKey => Key::J,
+		KKey => Key::K,
+		LKey => Key::L,
+		MKey => Key::M,
+		NKey => Key::N,
+		OKey => Key::O,
+		PKey => Key::P,
+		QKey => Key::Q,
+		RKey => Key::R,
+		SKey => Key::S,
+		TKey => Key::T,
+		UKey => Key::U,
+		VKey => Key::V,
+		WKey => Key::W,
+		XKey => Key::X,
+		YKey => Key::Y,
+		ZKey => Key::Z,
+		F1Key => Key::F1,
+		F2Key => Key::F2,
+		F3Key => Key::F3,
+		F4Key => Key::F4,
+		F5Key => Key::F5,
+		F6Key => Key::F6,
+		F7Key => Key::F7,
+		F8Key => Key::F8,
+		F9Key => Key::F9,
+		F10Key => Key::F10,
+		F11Key => Key::F11,
+		F12Key => Key::F12,
+		F13Key => Key::F13,
+		F14Key => Key::F14,
+		F15Key => Key::F15,
+		F16Key => Key::F16,
+		F17Key => Key::F17,
+		F18Key => Key::F18,
+		F19Key => Key::F19,
+		F20Key => Key::F20,
+		F21Key => Key::F21,
+		F22Key => Key::F22,
+		F23Key => Key::F23,
+		F24Key => Key::F24,
+		BackquoteKey => Key::Backtick,
+		SlashKey => Key::Slash,
+		BackslashKey => Key::Backslash,
+		CommaKey => Key::Comma,
+		PeriodKey => Key::Period,
+		MinusKey => Key::Minus,
+		QuoteKey => Key::Quote,
+		SemicolonKey => Key::Semicolon,
+		LBracketKey => Key::OpenBracket,
+		RBracketKey => Key::CloseBracket,
+		EqualKey => Key::Equals,
+		_ => return None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default_keybinds_have_no_conflicts() {
+		assert!(Keybinds::default().conflicts(&[]).is_empty());
+	}
+
+	#[test]
+	fn actions_sharing_a_key_conflict() {
+		let keybinds = Keybinds {
+			stop: Some(Key::F6.into()),
+			..Keybinds::default()
+		};
+		assert_eq!(keybinds.conflicts(&[]), vec![HotkeyAction::Toggle, HotkeyAction::Stop]);
+	}
+
+	#[test]
+	fn the_same_key_with_other_modifiers_is_no_conflict() {
+		let keybinds = Keybinds {
+			start: Some(Hotkey::new(Key::F6, Modifiers::CTRL)),
+			stop: Some(Hotkey::new(Key::F6, Modifiers::SHIFT)),
+			stop_all: Some(Hotkey::new(Key::F6, Modifiers::ALT)),
+			..Keybinds::default()
+		};
+		assert!(keybinds.conflicts(&[]).is_empty());
+	}
+
+	#[test]
+	fn a_key_the_clicker_presses_conflicts() {
+		let keybinds = Keybinds::default();
+		assert_eq!(keybinds.conflicts(&[Key::F6.into()]), vec![HotkeyAction::Toggle]);
+		assert!(keybinds.conflicts(&[Hotkey::new(Key::F6, Modifiers::CTRL)]).is_empty());
+	}
+}
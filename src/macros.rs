@@ -0,0 +1,47 @@
+//! Macros are recorded sequences of clicks that are played back on loop
+//! instead of clicking on an interval.
+
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::MouseButton;
+
+/// The longest pause a macro action can wait before it runs.
+pub const MAX_DELAY_MS: u32 = 3_600_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct MacroAction {
+	/// The button to click, or nothing to only move the cursor
+	#[serde(default)]
+	pub button: Option<MouseButton>,
+	pub x: i32,
+	pub y: i32,
+	/// How long to wait after the previous action before this one
+	pub delay_ms: u32,
+}
+
+/// Reads and validates a macro from a JSON file holding a list of actions.
+pub fn load(path: &Path) -> Result<Vec<MacroAction>, String> {
+	let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+	let actions: Vec<MacroAction> = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+	if actions.is_empty() {
+		return Err("The macro has no actions".to_owned());
+	}
+	validate(&actions)?;
+
+	Ok(actions)
+}
+
+pub fn save(path: &Path, actions: &[MacroAction]) -> Result<(), String> {
+	let text = serde_json::to_string_pretty(actions).map_err(|e| e.to_string())?;
+	fs::write(path, text).map_err(|e| e.to_string())
+}
+
+pub fn validate(actions: &[MacroAction]) -> Result<(), String> {
+	if let Some(i) = actions.iter().position(|action| action.delay_ms > MAX_DELAY_MS) {
+		return Err(format!("Action {} waits longer than an hour", i + 1));
+	}
+
+	Ok(())
+}
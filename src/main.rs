@@ -1,57 +1,286 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod audio;
+mod backend;
+mod control;
+mod crash;
+mod expression;
+mod gamepad;
+mod keybinds;
+mod macros;
+mod platform;
+mod profiles;
+mod settings;
+mod synthetic;
+mod template;
+mod toast;
+mod whats_new;
+
 use std::{
+    collections::VecDeque,
     sync::{Arc, Mutex, MutexGuard},
     thread::{self, sleep},
     time::{Duration, Instant},
 };
 
+use backend::{ClickBackend, ClickBackendKind, SendInputBackend};
 use eframe::{
     egui::{
-        self, Align2, Color32, FontDefinitions, FontFamily, IconData, Image, KeyboardShortcut,
-        Layout, Margin, Rect, Response, RichText, Rounding, Sense, Vec2,
+        self, Align2, Color32, FontDefinitions, FontFamily, IconData, Image, Layout, Margin, Rect, Response, RichText, Rounding, Sense, Vec2,
     },
     CreationContext,
 };
 use egui_extras::{Column, TableBuilder};
 use image::GenericImageView;
+use gamepad::{GamepadButton, GamepadMode, ReleaseAction};
 use inputbot::KeybdKey;
-use mouse_rs::Mouse;
-use rand::Rng;
+use keybinds::{Hotkey, HotkeyAction, Keybinds};
+use macros::MacroAction;
+use mouse_rs::{types::keys::Keys, Mouse};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use settings::{SavedTabs, Settings};
+use toast::{ToastLevel, Toasts};
 use strum::IntoEnumIterator;
 use strum_macros::{AsRefStr, EnumIter};
 
-#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug)]
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
 enum MouseButton {
     Left,
     Right,
     Middle,
 }
 
-#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug)]
+impl MouseButton {
+	fn key(&self) -> Keys {
+		match self {
+			MouseButton::Left => Keys::LEFT,
+			MouseButton::Middle => Keys::MIDDLE,
+			MouseButton::Right => Keys::RIGHT,
+		}
+	}
+
+	/// The button for one reported by the global hook, if it's one we can click.
+	fn from_hook(button: inputbot::MouseButton) -> Option<MouseButton> {
+		match button {
+			inputbot::MouseButton::LeftButton => Some(MouseButton::Left),
+			inputbot::MouseButton::RightButton => Some(MouseButton::Right),
+			inputbot::MouseButton::MiddleButton => Some(MouseButton::Middle),
+			_ => None,
+		}
+	}
+}
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum ActionType {
+    Click,
+    #[strum(serialize = "Anti-Idle")]
+    AntiIdle,
+    #[strum(serialize = "Click + Anti-Idle")]
+    ClickAndAntiIdle,
+    Scroll,
+}
+
+impl ActionType {
+	fn clicks(&self) -> bool {
+		matches!(self, ActionType::Click | ActionType::ClickAndAntiIdle)
+	}
+
+	fn nudges(&self) -> bool {
+		matches!(self, ActionType::AntiIdle | ActionType::ClickAndAntiIdle)
+	}
+}
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum ScrollDirection {
+	Up,
+	Down,
+}
+
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
 enum ClickMode {
     Single,
     Double,
+    /// A double click timed and placed so the OS is sure to recognize it
+    #[strum(serialize = "Smart Double")]
+    SmartDouble,
     Toggle,
 }
 
-#[derive(AsRefStr, PartialEq, EnumIter, Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct ClickStep {
+    mouse_button: MouseButton,
+    click_mode: ClickMode,
+    repeat: u32,
+}
+
+enum ClickTarget {
+    /// Click wherever the cursor currently is
+    Cursor,
+    Position(i32, i32),
+    /// The target can't be clicked right now, with the reason why
+    Unavailable(&'static str),
+    /// There's nothing to click right now, but it's not worth a warning
+    Skip,
+}
+
+/// How the cursor moves to the next click target.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum MovementEasing {
+    /// Jumps straight to the target, the fastest option
+    Instant,
+    Linear,
+    #[strum(serialize = "Ease In-Out")]
+    EaseInOut,
+}
+
+impl MovementEasing {
+	/// How far along the path the cursor is at `t`, both from 0 to 1.
+	fn apply(&self, t: f32) -> f32 {
+		match self {
+			MovementEasing::Instant => 1.0,
+			MovementEasing::Linear => t,
+			MovementEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+		}
+	}
+}
+
+#[derive(AsRefStr, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
 enum LimitMode {
     None,
     Clicks,
     Time,
+    /// Stop once the watched pixel no longer has the color it had at the start
+    #[strum(serialize = "Pixel Change")]
+    PixelChange,
+}
+
+/// What else happens once a limit stops the clicker.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum LimitAction {
+    /// Only stop, like before
+    Stop,
+    /// Stop and minimize the window
+    Minimize,
+    /// Stop and close Clickr, for one-shot runs from scripts
+    #[strum(serialize = "Quit Clickr")]
+    Quit,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Page {
+    Home,
+    Keybinds,
+}
+
+#[derive(AsRefStr, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum RegionMode {
+    None,
+    /// Click a new random point inside the region every time
+    Random,
 }
 
-#[derive(AsRefStr, PartialEq, EnumIter, Clone, Copy, Debug)]
+/// How Color Mode decides whether two colors are close enough.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum ColorMetric {
+    /// The straight line distance between the colors, every channel counts the same
+    #[strum(serialize = "RGB Distance")]
+    Rgb,
+    /// Separate tolerances for hue, saturation and brightness
+    #[strum(serialize = "HSV")]
+    Hsv,
+}
+
+/// What a running clicker does when its window is focused.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum FocusBehavior {
+    /// Stop clicking until the window loses focus again
+    #[strum(serialize = "Wait")]
+    Pause,
+    /// Turn the clicker off
+    Stop,
+}
+
+/// The corner of the screen the window is kept in.
+#[derive(AsRefStr, Eq, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
+enum WindowCorner {
+    /// The window stays wherever it's moved
+    None,
+    #[strum(serialize = "Top Left")]
+    TopLeft,
+    #[strum(serialize = "Top Right")]
+    TopRight,
+    #[strum(serialize = "Bottom Left")]
+    BottomLeft,
+    #[strum(serialize = "Bottom Right")]
+    BottomRight,
+}
+
+/// The corner, margin, work area and window size the window is moved for, so
+/// it's only moved again when one of them changes.
+type WindowAnchor = (WindowCorner, u32, (i32, i32, i32, i32), (i32, i32));
+
+#[derive(AsRefStr, PartialEq, EnumIter, Clone, Copy, Debug, Serialize, Deserialize)]
 enum IntervalMode {
     Constant,
     Random,
 }
 
 const COMPACT_WINDOW_SIZE: Vec2 = Vec2::new(240.0, 80.0);
+const DOT_WINDOW_SIZE: Vec2 = Vec2::new(24.0, 24.0);
+/// Room for the dot's right-click menu next to it.
+const DOT_MENU_WINDOW_SIZE: Vec2 = Vec2::new(180.0, 150.0);
 const WINDOW_SIZE: Vec2 = Vec2::new(400.0, 410.0);
-const TOGGLE_AUTO_CLICKER_SHORTCUT: KeyboardShortcut =
-    egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F6);
+const POSITION_MARKER_SIZE: f32 = 24.0;
+/// The least opaque the backdrop behind the shield's stats can be.
+const SHIELD_TEXT_ALPHA: u8 = 200;
+/// Width and height of the area shown by the magnifier. Odd so the cursor has a center pixel.
+const MAGNIFIER_PIXELS: u32 = 15;
+const MAGNIFIER_ZOOM: f32 = 8.0;
+const CLICK_HISTORY_SIZE: usize = 50;
+const FULLSCREEN_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+/// The longest pause between the presses and releases of a smart double click.
+const SMART_DOUBLE_CLICK_STEP: Duration = Duration::from_millis(15);
+/// The longest interval in either mode, what the hours, minutes, seconds and
+/// milliseconds fields can hold. Random intervals share it, so switching modes
+/// never changes how long an interval can be.
+const MAX_INTERVAL: f64 = 23.0 * 3600.0 + 59.0 * 60.0 + 59.999;
+/// `MAX_INTERVAL` for the random interval fields, as close as an `f32` gets.
+const MAX_RANDOM_INTERVAL: f32 = MAX_INTERVAL as f32;
+const MAX_CONSTANT_INTERVAL_MS: u64 = 24 * 3_600_000 - 1;
+/// How far from the primary monitor's corner a screen position can be. Windows
+/// keeps the whole virtual screen within this, so a click region inside it
+/// can't overflow when a position in it is picked.
+const MAX_SCREEN_COORDINATE: i32 = 32_768;
+/// The range of the interval slider, a click every 10 seconds to one every millisecond.
+const SLIDER_MIN_CPS: f64 = 0.1;
+const SLIDER_MAX_CPS: f64 = 1000.0;
+/// How often the cursor is moved while easing towards a target.
+const MOVEMENT_STEP: Duration = Duration::from_millis(5);
+/// How often the cursor position is checked while waiting for a move to land.
+const CURSOR_VERIFY_STEP: Duration = Duration::from_millis(1);
+/// How long the window border glows after a click, with Click Flash on. A new
+/// flash only starts once the last one is over, so fast clicking blinks
+/// instead of glowing steadily.
+const CLICK_FLASH_DURATION: Duration = Duration::from_millis(150);
+/// How the window looks before anything is applied to it: opaque and in the taskbar.
+const DEFAULT_WINDOW_STYLE: (f32, bool) = (1.0, true);
+/// Starting with this argument leaves the keyboard unhooked.
+const NO_GLOBAL_HOTKEYS_FLAG: &str = "--no-global-hotkeys";
+const BENCHMARK_DURATION: Duration = Duration::from_secs(1);
+/// How often the screen is checked while color mode is paused.
+const COLOR_PAUSED_POLL_INTERVAL: f64 = 0.25;
+/// Sessions the click limit ends sooner than this, in seconds, are warned about.
+const SHORT_SESSION_WARNING: f64 = 0.5;
+/// How often the battery is checked while Low Battery Pause is on.
+const BATTERY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// How often the first click is retried while it's held back, with Click Immediately on.
+const PRIME_CLICK_POLL_INTERVAL: f64 = 0.01;
+/// The shortest interval allowed while safe mode is on.
+const SAFE_MODE_MIN_INTERVAL: f64 = 0.001;
+/// How much longer than requested a sleep has to take before we assume the
+/// system was suspended in between.
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(5);
 
 fn percentage_distance_between_colors(a: Color32, b: Color32) -> f32 {
     let distance_r = a.r().abs_diff(b.r()) as f32;
@@ -64,6 +293,266 @@ fn percentage_distance_between_colors(a: Color32, b: Color32) -> f32 {
     percentage
 }
 
+/// Hue in degrees, saturation and value from 0 to 1.
+fn rgb_to_hsv(color: Color32) -> (f32, f32, f32) {
+	let r = color.r() as f32 / 255.0;
+	let g = color.g() as f32 / 255.0;
+	let b = color.b() as f32 / 255.0;
+
+	let max = r.max(g).max(b);
+	let min = r.min(g).min(b);
+	let delta = max - min;
+
+	let hue = if delta == 0.0 {
+		0.0
+	} else if max == r {
+		60.0 * ((g - b) / delta).rem_euclid(6.0)
+	} else if max == g {
+		60.0 * ((b - r) / delta + 2.0)
+	} else {
+		60.0 * ((r - g) / delta + 4.0)
+	};
+	let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+	(hue, saturation, max)
+}
+
+/// Whether each of hue, saturation and value are within their tolerance. The
+/// hue tolerance is in degrees, the others are percentages.
+fn hsv_colors_match(a: Color32, b: Color32, tolerance: (u16, u8, u8)) -> bool {
+	let (hue_a, saturation_a, value_a) = rgb_to_hsv(a);
+	let (hue_b, saturation_b, value_b) = rgb_to_hsv(b);
+
+	// Grays have no hue, so any hue matches them
+	let hue_distance = (hue_a - hue_b).abs();
+	let hue_matches = saturation_a == 0.0
+		|| saturation_b == 0.0
+		|| hue_distance.min(360.0 - hue_distance) <= tolerance.0 as f32;
+
+	hue_matches
+		&& (saturation_a - saturation_b).abs() * 100.0 <= tolerance.1 as f32
+		&& (value_a - value_b).abs() * 100.0 <= tolerance.2 as f32
+}
+
+/// A darker frame behind the shield's stats, so they stay readable when the
+/// shield itself is see-through.
+fn shield_backdrop(opacity: u8) -> egui::Frame {
+	egui::Frame::none()
+		.fill(Color32::from_black_alpha(opacity.max(SHIELD_TEXT_ALPHA)))
+		.rounding(Rounding::same(4.0))
+		.inner_margin(Margin::same(4.0))
+}
+
+/// The color of the pixel at the given screen position in pixels.
+fn screen_color(x: i32, y: i32) -> Option<Color32> {
+	// autopilot works in points, which differ from pixels on scaled displays
+	let scale = autopilot::screen::scale();
+	let point = autopilot::geometry::Point::new(x as f64 / scale, y as f64 / scale);
+	let pixel = autopilot::screen::get_color(point).ok()?;
+
+	Some(Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]))
+}
+
+/// A click limit below 1 is treated as 1 so a session always gets to click at least once.
+fn click_limit_reached(clicks: u32, limit: u32) -> bool {
+	clicks >= limit.max(1)
+}
+
+/// The time a sleep overshot by if it was long enough to mean the system was
+/// suspended, like when a laptop lid is closed.
+fn suspended_time(requested: Duration, slept: Duration) -> Option<Duration> {
+	let overshoot = slept.checked_sub(requested)?;
+	(overshoot >= SUSPEND_THRESHOLD).then_some(overshoot)
+}
+
+/// The random source for a session. A fixed seed makes every run pick the
+/// same random intervals, buttons and positions.
+fn session_rng(seed: Option<u32>) -> StdRng {
+	match seed {
+		Some(seed) => StdRng::seed_from_u64(seed as u64),
+		None => StdRng::from_entropy(),
+	}
+}
+
+/// A random interval in seconds between `min` and `max`.
+fn random_interval(rng: &mut StdRng, min: f32, max: f32) -> f64 {
+	rng.gen_range(min as f64..=max as f64)
+}
+
+/// One of `buttons` at random, or `fallback` when there are none to pick from.
+fn random_mouse_button(rng: &mut StdRng, buttons: &[MouseButton], fallback: MouseButton) -> MouseButton {
+	buttons.choose(rng).copied().unwrap_or(fallback)
+}
+
+/// What a Toggle click sends, depending on whether its button is held down.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ToggleInput {
+	Press,
+	Release,
+}
+
+impl ToggleInput {
+	/// The input for the next Toggle click, starting with a press.
+	fn next(mouse_is_pressed: bool) -> ToggleInput {
+		if mouse_is_pressed {
+			ToggleInput::Release
+		} else {
+			ToggleInput::Press
+		}
+	}
+
+	/// Whether the button is held down after sending this input from the
+	/// `mouse_is_pressed` state. Only an input that went through changes it, so
+	/// a failed release still counts as held and is retried on the next start
+	/// or stop.
+	fn held_after<T, E>(self, mouse_is_pressed: bool, result: &Result<T, E>) -> bool {
+		match result {
+			Ok(_) => self == ToggleInput::Press,
+			Err(_) => mouse_is_pressed,
+		}
+	}
+}
+
+/// When the next click is due with smooth timing. Counting from the previous
+/// deadline instead of from `now` keeps the time spent clicking and waiting for
+/// the lock from adding up. A deadline that has already passed is caught up on
+/// right away, but more than a whole click behind, the missed clicks are
+/// dropped instead of clicked all at once.
+fn smooth_deadline(previous: Option<Instant>, now: Instant, interval: Duration, post_click_delay: Duration) -> Instant {
+	let deadline = previous.unwrap_or(now) + post_click_delay + interval;
+	if deadline + interval < now {
+		now
+	} else {
+		deadline
+	}
+}
+
+/// Whether `minute` (of the day) falls between `start` and `end`, including
+/// the start but not the end. A window that ends before it starts runs past
+/// midnight, and one that ends when it starts lasts all day.
+fn in_time_window(minute: u32, start: u32, end: u32) -> bool {
+	match start.cmp(&end) {
+		std::cmp::Ordering::Less => (start..end).contains(&minute),
+		std::cmp::Ordering::Greater => minute >= start || minute < end,
+		std::cmp::Ordering::Equal => true,
+	}
+}
+
+/// The interval for the nearest whole number of clicks per second, rounded to
+/// the millisecond. If that's the rate of the `previous` interval, it moves
+/// on to the next rate in the direction of the change, so dragging the fields
+/// doesn't get stuck. Intervals of a second or longer are left alone, since
+/// there is no whole number of clicks per second below one.
+fn snap_to_cps(seconds: f64, previous: f64) -> f64 {
+	if seconds <= 0.0 || seconds >= 1.0 {
+		return seconds;
+	}
+
+	let mut cps = (1.0 / seconds).round();
+	if previous > 0.0 && cps == (1.0 / previous).round() {
+		cps += if seconds < previous { 1.0 } else { -1.0 };
+	}
+	(1000.0 / cps.max(1.0)).round() / 1000.0
+}
+
+/// Parses one `x,y` coordinate per line, returning the parsed points and the
+/// (1-based) line numbers that could not be parsed. Blank lines are ignored.
+fn parse_click_points(text: &str) -> (Vec<(i32, i32)>, Vec<usize>) {
+	let mut points = Vec::new();
+	let mut skipped = Vec::new();
+
+	for (i, line) in text.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() {
+			continue;
+		}
+
+		let point = line.split_once(',').and_then(|(x, y)| {
+			Some((x.trim().parse::<i32>().ok()?, y.trim().parse::<i32>().ok()?))
+		});
+
+		match point {
+			Some(point) => points.push(point),
+			None => skipped.push(i + 1),
+		}
+	}
+
+	(points, skipped)
+}
+
+struct IntervalStats {
+	min: f64,
+	max: f64,
+	mean: f64,
+	std_dev: f64,
+}
+
+impl IntervalStats {
+	/// Statistics over click intervals (in seconds), or nothing without any intervals.
+	fn new(intervals: &[f64]) -> Option<IntervalStats> {
+		if intervals.is_empty() {
+			return None;
+		}
+
+		let count = intervals.len() as f64;
+		let mean = intervals.iter().sum::<f64>() / count;
+		let variance = intervals.iter().map(|interval| (interval - mean).powi(2)).sum::<f64>() / count;
+
+		Some(IntervalStats {
+			min: intervals.iter().copied().fold(f64::INFINITY, f64::min),
+			max: intervals.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+			mean,
+			std_dev: variance.sqrt(),
+		})
+	}
+
+	fn summary(&self) -> String {
+		format!(
+			"avg {:.1}ms, std dev {:.1}ms, {:.1} - {:.1}ms",
+			self.mean * 1000.0,
+			self.std_dev * 1000.0,
+			self.min * 1000.0,
+			self.max * 1000.0,
+		)
+	}
+}
+
+/// Draws a sparkline of the given click intervals (in seconds), oldest on the left.
+fn interval_graph(ui: &mut egui::Ui, intervals: &[f64]) {
+	let (rect, _) = ui.allocate_exact_size(Vec2::new(150.0, 40.0), Sense::hover());
+	ui.painter().rect_stroke(
+		rect,
+		Rounding::same(3.0),
+		ui.style().visuals.widgets.noninteractive.bg_stroke,
+	);
+
+	if intervals.len() < 2 {
+		return;
+	}
+
+	let max = intervals.iter().copied().fold(f64::EPSILON, f64::max);
+	let points = intervals
+		.iter()
+		.enumerate()
+		.map(|(i, interval)| {
+			egui::pos2(
+				rect.left() + rect.width() * i as f32 / (intervals.len() - 1) as f32,
+				rect.bottom() - rect.height() * (interval / max) as f32,
+			)
+		})
+		.collect();
+	ui.painter().add(egui::Shape::line(
+		points,
+		egui::Stroke::new(1.5, Color32::from_rgb(0, 170, 255)),
+	));
+
+	ui.label(
+		RichText::new(format!("max {:.0}ms", max * 1000.0))
+			.small()
+			.color(ui.style().visuals.weak_text_color()),
+	);
+}
+
 fn tag_label(ui: &mut egui::Ui, text: &str, color: Color32, icon: Option<Image>) {
     egui::Frame::default()
         .fill(color)
@@ -86,6 +575,50 @@ fn tag_label(ui: &mut egui::Ui, text: &str, color: Color32, icon: Option<Image>)
         });
 }
 
+/// Whether a settings row with one of these labels matches the search, which
+/// has to be lowercase already. Every row matches an empty search.
+fn matches_search(search: &str, labels: &[&str]) -> bool {
+	search.is_empty() || labels.iter().any(|label| label.to_lowercase().contains(search))
+}
+
+/// A dot moving back and forth along a line with the easing, to show what it looks like.
+fn easing_preview(ui: &mut egui::Ui, easing: MovementEasing) {
+	let (rect, _) = ui.allocate_exact_size(Vec2::new(40.0, 12.0), Sense::hover());
+	let painter = ui.painter();
+	let color = ui.style().visuals.weak_text_color();
+	painter.line_segment([rect.left_center(), rect.right_center()], egui::Stroke::new(1.0, color));
+
+	// One second there, one second back
+	let time = (ui.input(|i| i.time) % 2.0) as f32;
+	let t = if time < 1.0 { time } else { 2.0 - time };
+	let x = rect.left() + rect.width() * easing.apply(t);
+	painter.circle_filled(egui::pos2(x, rect.center().y), 3.0, Color32::from_rgb(0, 170, 255));
+}
+
+/// Buttons that halve or double the interval, usable while clicking.
+fn speed_buttons(ui: &mut egui::Ui, app: &mut App) {
+	ui.horizontal(|ui| {
+		if ui.small_button("0.5x").on_hover_text("Halves the speed by doubling the interval").clicked() {
+			app.scale_interval(2.0);
+		}
+		if ui.small_button("2x").on_hover_text("Doubles the speed by halving the interval").clicked() {
+			app.scale_interval(0.5);
+		}
+	});
+}
+
+/// Shown while the clicker is enabled but hasn't clicked yet, pulsing so it
+/// doesn't look like the clicker froze.
+fn armed_tag(ui: &mut egui::Ui) {
+	let pulse = (ui.input(|i| i.time) * 4.0).sin() as f32 * 0.5 + 0.5;
+	tag_label(
+		ui,
+		"ARMED - WAITING TO START",
+		Color32::from_rgb(0, 170, 255).gamma_multiply(0.4 + 0.6 * pulse),
+		None,
+	);
+}
+
 fn beta_tag(ui: &mut egui::Ui) {
     tag_label(ui, "BETA", Color32::from_rgb(0, 170, 255), None);
 }
@@ -138,23 +671,119 @@ fn big_header(ui: &mut egui::Ui, text: &str, image: Image) {
     ui.add_space(10.0);
 }
 
-fn show_constant_interval_mode(ui: &mut egui::Ui, h: &mut u32, m: &mut u32, s: &mut u32, ms: &mut u32) {
+/// A text field for typing an exact number. The value only changes once Enter
+/// is pressed or the field loses focus, and anything that isn't a number in
+/// `range` is thrown away.
+fn number_field<N: egui::emath::Numeric>(
+	ui: &mut egui::Ui,
+	value: &mut N,
+	range: std::ops::RangeInclusive<f64>,
+	suffix: &str,
+) -> Response {
+	let id = ui.next_auto_id();
+	let mut text = ui
+		.data_mut(|d| d.get_temp::<String>(id))
+		.unwrap_or_else(|| value.to_f64().to_string());
+
+	let response = ui.add(egui::TextEdit::singleline(&mut text).id(id).desired_width(f32::INFINITY));
+
+	if response.lost_focus() {
+		let parsed = text.trim().trim_end_matches(suffix).trim().parse::<f64>();
+		if let Ok(number) = parsed {
+			if range.contains(&number) && (!N::INTEGRAL || number.fract() == 0.0) {
+				*value = N::from_f64(number);
+			}
+		}
+		ui.data_mut(|d| d.remove::<String>(id));
+	} else if response.has_focus() {
+		ui.data_mut(|d| d.insert_temp(id, text));
+	}
+
+	response
+}
+
+/// The interval in seconds for an expression like `1000/15 ms` or `1/8 s`.
+/// Without a unit it's in seconds.
+fn interval_from_expression(text: &str) -> Result<f64, String> {
+	let text = text.trim();
+	let seconds = if let Some(millis) = text.strip_suffix("ms") {
+		expression::evaluate(millis)? / 1000.0
+	} else {
+		expression::evaluate(text.strip_suffix('s').unwrap_or(text))?
+	};
+
+	if !(0.0..=MAX_INTERVAL).contains(&seconds) {
+		return Err("The interval is out of range".to_owned());
+	}
+	Ok(seconds)
+}
+
+/// Carries minutes, seconds and milliseconds past their unit over into the
+/// next one, so 1500ms becomes 1s 500ms. Anything past 23:59:59.999 is capped.
+fn normalize_interval(h: &mut u32, m: &mut u32, s: &mut u32, ms: &mut u32) {
+	let total = *h as u64 * 3_600_000 + *m as u64 * 60_000 + *s as u64 * 1000 + *ms as u64;
+	let total = total.min(MAX_CONSTANT_INTERVAL_MS) as u32;
+
+	*h = total / 3_600_000;
+	*m = total / 60_000 % 60;
+	*s = total / 1000 % 60;
+	*ms = total % 1000;
+}
+
+fn show_constant_interval_mode(ui: &mut egui::Ui, h: &mut u32, m: &mut u32, s: &mut u32, ms: &mut u32, typing: bool) {
+	// The smaller units take more than they can hold and are carried over after
+	const MAX_M: u32 = 24 * 60 - 1;
+	const MAX_S: u32 = 24 * 3600 - 1;
+	const MAX_MS: u32 = MAX_CONSTANT_INTERVAL_MS as u32;
+
 	ui.columns(4, |columns| {
-		columns[0].add(egui::DragValue::new(h).range(0..=23).suffix("h"));
-		columns[1].add(egui::DragValue::new(m).range(0..=59).suffix("m"));
-		columns[2].add(egui::DragValue::new(s).range(0..=59).suffix("s"));
-		columns[3].add(egui::DragValue::new(ms).range(0..=999).suffix("ms"));
+		if typing {
+			number_field(&mut columns[0], h, 0.0..=23.0, "h");
+			number_field(&mut columns[1], m, 0.0..=MAX_M as f64, "m");
+			number_field(&mut columns[2], s, 0.0..=MAX_S as f64, "s");
+			number_field(&mut columns[3], ms, 0.0..=MAX_MS as f64, "ms");
+			return;
+		}
+
+		columns[0].add(egui::DragValue::new(h).range(0..=23).suffix("h").update_while_editing(false));
+		columns[1].add(egui::DragValue::new(m).range(0..=MAX_M).suffix("m").update_while_editing(false));
+		columns[2].add(egui::DragValue::new(s).range(0..=MAX_S).suffix("s").update_while_editing(false));
+		columns[3].add(egui::DragValue::new(ms).range(0..=MAX_MS).suffix("ms").update_while_editing(false));
 	});
+
+	normalize_interval(h, m, s, ms);
 }
 
-fn show_random_interval_mode(ui: &mut egui::Ui, min: &mut f32, max: &mut f32) {
+/// A single clicks per second slider for the constant interval, spaced
+/// logarithmically so both ends are as easy to reach. Returns whether it changed.
+fn show_cps_slider(ui: &mut egui::Ui, settings: &mut Settings) -> bool {
+	let interval = settings.constant_interval();
+	let mut cps = if interval > 0.0 { 1.0 / interval } else { SLIDER_MAX_CPS };
+
+	let changed = ui
+		.add(
+			egui::Slider::new(&mut cps, SLIDER_MIN_CPS..=SLIDER_MAX_CPS)
+				.logarithmic(true)
+				.suffix(" CPS")
+				.max_decimals(2),
+		)
+		.changed();
+	if changed {
+		settings.set_constant_interval(1.0 / cps);
+	}
+
+	ui.label(
+		RichText::new(format!("One click every {:.3}s", settings.constant_interval()))
+			.small()
+			.color(ui.style().visuals.weak_text_color()),
+	);
+
+	changed
+}
+
+fn show_random_interval_mode(ui: &mut egui::Ui, min: &mut f32, max: &mut f32, typing: bool) {
 	ui.columns(2, |columns| {
-		// Clamp max between 0.0 and 3600.0
-		if *max > 3600.0 {
-			*max = 3600.0;
-		} else if *max < 0.0 {
-			*max = 0.0;
-		}
+		*max = max.clamp(0.0, MAX_RANDOM_INTERVAL);
 
 		// Clamp min between 0.0 and max
 		if *min > *max {
@@ -165,11 +794,16 @@ fn show_random_interval_mode(ui: &mut egui::Ui, min: &mut f32, max: &mut f32) {
 
 		let fields = [min, max];
 		fields.into_iter().enumerate().for_each(|(i, value)| {
+			if typing {
+				number_field(&mut columns[i], value, 0.0..=MAX_INTERVAL, "s");
+				return;
+			}
+
 			columns[i].add(egui::DragValue::new(value)
 				.suffix("s")
 				.speed(0.1)
 				.min_decimals(1)
-				.range(0..=3600)
+				.range(0.0..=MAX_RANDOM_INTERVAL)
 				.update_while_editing(false)
 				.max_decimals(3),
 			);
@@ -177,6 +811,17 @@ fn show_random_interval_mode(ui: &mut egui::Ui, min: &mut f32, max: &mut f32) {
 	});
 }
 
+/// The window's size for the Dot Mode and Compact Mode settings.
+fn window_size(settings: &Settings) -> Vec2 {
+    if settings.dot_mode {
+        DOT_WINDOW_SIZE
+    } else if settings.compact_mode {
+        COMPACT_WINDOW_SIZE
+    } else {
+        WINDOW_SIZE
+    }
+}
+
 fn main() -> Result<(), eframe::Error> {
     let (icon_rgba, icon_width, icon_height) = {
         let image = image::load_from_memory_with_format(
@@ -212,59 +857,239 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
-struct AppHolder {
-    main_app: Arc<Mutex<App>>,
-}
+/// Clicks the Click Burst hotkey's clicks on a thread of their own, with the
+/// clicker's mouse button and click mode. It doesn't touch the clicker's
+/// session, and the lock is only held for each click so the UI stays responsive.
+fn spawn_key_burst(app_arc: Arc<Mutex<App>>) {
+    thread::spawn(move || {
+        let (clicks, interval) = {
+            let app = app_arc.lock().unwrap();
+            (app.settings.key_burst_clicks, Duration::from_millis(app.settings.key_burst_interval_ms as u64))
+        };
 
-impl AppHolder {
-    fn new(cc: &CreationContext<'_>) -> Self {
-        let new_app = App {
-            mouse: Mouse::new(),
+        for i in 0..clicks {
+            if i > 0 {
+                sleep(interval);
+            }
 
-            interval_mode: IntervalMode::Constant,
-            hours: 0,
-            minutes: 0,
-            seconds: 0,
-            milliseconds: 100,
+            let mut app = app_arc.lock().unwrap();
+            if app.key_burst_cancelled {
+                break;
+            }
+            // Holding the button down doesn't make sense in a burst
+            let click_mode = match app.settings.click_mode {
+                ClickMode::Toggle => ClickMode::Single,
+                click_mode => click_mode,
+            };
+            synthetic::mark();
+            let result = app.click_mouse(app.settings.mouse_button, click_mode);
+            synthetic::mark();
+            if let Err(error) = result {
+                app.input_error = Some(error);
+                app.toast(ToastLevel::Danger, "Click burst failed");
+                break;
+            }
+        }
 
-            interval_mode_random_min: 1.0,
-            interval_mode_random_max: 2.0,
+        let mut app = app_arc.lock().unwrap();
+        app.key_burst_running = false;
+        app.key_burst_cancelled = false;
+    });
+}
 
-            mouse_button: MouseButton::Left,
-            click_mode: ClickMode::Single,
+/// All clicker instances. Each one has its own lock so running clickers never
+/// wait on each other.
+struct Clickers {
+    list: Vec<Arc<Mutex<App>>>,
+    selected: usize,
+    next_number: u32,
+}
 
-            mouse_is_pressed: false,
+impl Clickers {
+    fn selected(&self) -> &Arc<Mutex<App>> {
+        &self.list[self.selected]
+    }
 
-            clicker_id: 0,
+    fn for_each(&self, mut f: impl FnMut(&mut App)) {
+        for app in &self.list {
+            f(&mut app.lock().unwrap());
+        }
+    }
 
-            color_mode: false,
-            color_mode_color: Color32::BLACK,
-            hovering_pixel_color: Color32::BLACK,
+    /// Runs the action bound to `hotkey` in the selected clicker's keybinds.
+    /// If it has none, clickers with Background Hotkeys on get to start or
+    /// stop themselves with their own keys.
+    fn handle_hotkey(&self, hotkey: Hotkey, ctx: &egui::Context) {
+        let mut app = self.selected().lock().unwrap();
+        // The key is being assigned, not used
+        if app.capturing_hotkey.is_some() {
+            return;
+        }
+        let Some(action) = app.settings.keybinds.action_for(hotkey) else {
+            drop(app);
+            for (i, app) in self.list.iter().enumerate() {
+                if i == self.selected {
+                    continue;
+                }
+                let mut app = app.lock().unwrap();
+                if !app.settings.keybinds.background {
+                    continue;
+                }
+                match app.settings.keybinds.action_for(hotkey) {
+                    Some(HotkeyAction::Toggle) => app.toggle_clicker(),
+                    Some(HotkeyAction::Start) if !app.clicker_enabled => app.toggle_clicker(),
+                    Some(HotkeyAction::Stop) => {
+                        app.clicker_enabled = false;
+                        app.cancel_key_burst();
+                    }
+                    _ => {}
+                }
+            }
+            return;
+        };
 
-            limit_mode: LimitMode::None,
-            limit_mode_clicks_amount: 10,
-            color_mode_distance_threshold: 0,
-            limit_mode_time: 1.0,
+        match action {
+            HotkeyAction::Toggle => app.toggle_clicker(),
+            HotkeyAction::Start => {
+                if !app.clicker_enabled {
+                    app.toggle_clicker();
+                }
+            }
+            HotkeyAction::Stop => {
+                app.clicker_enabled = false;
+                app.cancel_key_burst();
+            }
+            HotkeyAction::StopAll => {
+                let recenter = app.settings.stop_all_recenter;
+                drop(app);
+                self.for_each(|app| {
+                    app.clicker_enabled = false;
+                    app.cancel_key_burst();
+                });
+                if recenter {
+                    self.selected().lock().unwrap().recenter_cursor();
+                }
+            }
+            HotkeyAction::CompactMode => {
+                app.settings.compact_mode = !app.settings.compact_mode;
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window_size(&app.settings)));
+            }
+            HotkeyAction::Burst => {
+                if !app.key_burst_running && app.mouse_available {
+                    app.key_burst_running = true;
+                    drop(app);
+                    spawn_key_burst(Arc::clone(self.selected()));
+                }
+            }
+            HotkeyAction::ColorMode => {
+                if !app.clicker_enabled && app.screen_available {
+                    app.toggle_color_mode();
+                }
+            }
+            HotkeyAction::SpeedUp => app.scale_interval(0.5),
+            HotkeyAction::SlowDown => app.scale_interval(2.0),
+        }
+    }
+}
 
-            clicker_enabled: false,
-            last_clicker_enabled: false,
-            clicker_start_time: Instant::now(),
-            total_clicks: 0,
+struct AppHolder {
+    /// The clicker that is currently shown
+    main_app: Arc<Mutex<App>>,
+    clickers: Arc<Mutex<Clickers>>,
+    toasts: Arc<Mutex<Toasts>>,
+    page: Page,
+    /// The opacity and taskbar visibility last applied to the window
+    window_style: (f32, bool),
+    /// Whether the keyboard is hooked, otherwise hotkeys only work in the window
+    global_hotkeys: bool,
+    /// What the window was last moved into a corner for
+    window_anchor: Option<WindowAnchor>,
+    /// Whether the changelog popup is open after an update
+    whats_new: bool,
+    /// The saved profiles, read when the Profiles menu opens
+    profile_names: Option<Vec<String>>,
+    /// Whether Save As is waiting for the user to confirm replacing a profile
+    confirming_profile_overwrite: bool,
+    /// The click count last seen and when the border last started flashing
+    click_flash: (u32, Option<Instant>),
+    /// When Clickr was opened, for the uptime in the menu bar
+    launched: Instant,
+}
 
-            always_on_top: true,
-            focused: true,
-            compact_mode: false,
-        };
+impl AppHolder {
+    fn new(cc: &CreationContext<'_>) -> Self {
+        let toasts = Arc::new(Mutex::new(Toasts::default()));
+        let (saved, load_problem) = SavedTabs::load_saved();
+        if let Some(problem) = load_problem {
+            toasts.lock().unwrap().push(ToastLevel::Warning, problem);
+        }
+        let list: Vec<_> = saved
+            .tabs
+            .into_iter()
+            .enumerate()
+            .map(|(i, settings)| Arc::new(Mutex::new(App::new(format!("Clicker {}", i + 1), settings, toasts.clone()))))
+            .collect();
+        let app_arc = list[saved.selected].clone();
+        let clickers = Arc::new(Mutex::new(Clickers {
+            next_number: list.len() as u32 + 1,
+            list,
+            selected: saved.selected,
+        }));
+
+        // Without a keyboard bind inputbot never hooks the keyboard, the mouse hooks still work
+        let global_hotkeys = !std::env::args().any(|arg| arg == NO_GLOBAL_HOTKEYS_FLAG);
+        if global_hotkeys {
+            let clickers_clone = clickers.clone();
+            let ctx = cc.egui_ctx.clone();
+            KeybdKey::bind_all(move |key| {
+                let clickers = clickers_clone.lock().unwrap();
+                clickers.for_each(|app| app.record_user_input());
+                if let Some(hotkey) = keybinds::hook_hotkey(key) {
+                    clickers.handle_hotkey(hotkey, &ctx);
+                }
+            });
+        }
+
+        // This has to come before the left button bind below, which replaces it
+        let clickers_clone = clickers.clone();
+        inputbot::MouseButton::bind_all(move |button| {
+            clickers_clone.lock().unwrap().for_each(|app| {
+                app.record_mouse_input();
+                if let Some(button) = MouseButton::from_hook(button) {
+                    app.record_macro_click(button);
+                }
+            });
+        });
 
-        let app_arc = Arc::new(Mutex::new(new_app));
-        let app_arc_clone = app_arc.clone();
+        let clickers_clone = clickers.clone();
 
-        KeybdKey::F6Key.bind(move || {
-            let mut app = app_arc_clone.lock().unwrap();
-            app.clicker_enabled = !app.clicker_enabled;
+        inputbot::MouseButton::LeftButton.bind(move || {
+            // A clicker clicking the left button shouldn't finish a capture in another tab
+            let synthetic = synthetic::is_recent();
+            clickers_clone.lock().unwrap().for_each(|app| {
+                app.record_mouse_input();
+                app.record_macro_click(MouseButton::Left);
+                if synthetic {
+                    return;
+                }
+                if app.capturing_fixed_position {
+                    app.capture_fixed_position();
+                } else if app.capturing_color {
+                    app.capture_color();
+                } else if app.capturing_watch_pixel {
+                    app.capture_watch_pixel();
+                } else if app.capturing_allowed_window {
+                    app.capture_allowed_window();
+                } else if app.capturing_click_point {
+                    app.capture_click_point();
+                }
+            });
         });
 
         thread::spawn(|| inputbot::handle_input_events());
+        gamepad::spawn(clickers.clone());
+        crash::install(clickers.clone());
+        control::spawn(clickers.clone());
 
         let mut fonts = FontDefinitions::default();
 
@@ -300,7 +1125,24 @@ impl AppHolder {
 
         cc.egui_ctx.set_fonts(fonts);
 
-        AppHolder { main_app: app_arc }
+        let holder = AppHolder {
+            main_app: app_arc,
+            clickers,
+            toasts,
+            page: Page::Home,
+            window_style: DEFAULT_WINDOW_STYLE,
+            global_hotkeys,
+            window_anchor: None,
+            whats_new: whats_new::check(),
+            profile_names: None,
+            confirming_profile_overwrite: false,
+            click_flash: (0, None),
+            launched: Instant::now(),
+        };
+        // The window is created at the full size, the saved settings may want it compact
+        holder.apply_window_settings(&cc.egui_ctx);
+
+        holder
     }
 
     fn app(&self) -> MutexGuard<App> {
@@ -311,6 +1153,7 @@ impl AppHolder {
     }
 
     fn click_shield(&mut self, ctx: &egui::Context) {
+        let opacity = self.app().settings.shield_opacity;
         egui::CentralPanel::default()
             .frame(egui::Frame::none().inner_margin(Margin::same(10.0)))
             .show(ctx, |ui| {
@@ -318,44 +1161,141 @@ impl AppHolder {
                 ui.painter().rect_filled(
                     ui.clip_rect(),
                     Rounding::ZERO,
-                    Color32::from_black_alpha(200),
+                    Color32::from_black_alpha(opacity),
                 );
                 egui::Image::new(egui::include_image!("./assets/Click.png")).paint_at(
                     ui,
                     Rect::from_center_size(ui.clip_rect().center(), [50.0, 50.0].into()),
                 );
-				egui::Grid::new("click_shield_grid").show(ui, |ui| {
+				shield_backdrop(opacity).show(ui, |ui| {
+					egui::Grid::new("click_shield_grid").show(ui, |ui| {
+						let app = self.app();
+
+						ui.label("Time");
+						ui.label(
+							RichText::new(format!("{:.2}", app.session_time().as_secs_f64()))
+								.color(ui.style().visuals.strong_text_color()),
+						);
+						ui.end_row();
+
+						ui.label("Clicks");
+						ui.label(
+							RichText::new(format!("{}", app.session_clicks))
+								.color(ui.style().visuals.strong_text_color()),
+						);
+						ui.end_row();
+
+						if app.settings.accumulate_clicks {
+							ui.label("Total");
+							ui.label(
+								RichText::new(format!("{}", app.total_clicks))
+									.color(ui.style().visuals.strong_text_color()),
+							);
+							ui.end_row();
+						}
+
+						if app.settings.show_click_position {
+							ui.label("Position");
+							ui.label(
+								RichText::new(app.last_click_pos.map_or("-".to_owned(), |(x, y)| format!("{}, {}", x, y)))
+									.color(ui.style().visuals.strong_text_color()),
+							);
+							ui.end_row();
+						}
+					});
+
 					let app = self.app();
+					let mut breakdown = format!("{} ticks, {} physical clicks", app.session_ticks, app.physical_clicks);
+					if app.settings.burst_enabled {
+						breakdown += &format!(", {} bursts", app.session_bursts);
+					}
+					if app.settings.attempt_limit_enabled {
+						breakdown += &format!(", {} / {} attempts", app.session_attempts, app.settings.attempt_limit);
+					}
+					drop(app);
+					ui.label(RichText::new(breakdown).small().color(ui.style().visuals.weak_text_color()))
+						.on_hover_text("Ticks: times the interval came around, even if nothing was clicked\nPhysical clicks: times a button went down, a double click is two\nBursts: bursts finished so far\nAttempts: times it tried to click, including before the first click");
 
-					ui.label("Time");
-					ui.label(
-						RichText::new(format!(
-							"{:.2}",
-							Instant::now()
-								.duration_since(app.clicker_start_time)
-								.as_secs_f64()
-						))
-						.color(ui.style().visuals.strong_text_color()),
-					);
-					ui.end_row();
+					speed_buttons(ui, &mut self.app_mut());
 
-					ui.label("Clicks");
-					ui.label(
-						RichText::new(format!("{}", app.total_clicks))
-							.color(ui.style().visuals.strong_text_color()),
-					);
-					ui.end_row();
+					let app = self.app();
+					let intervals = app.click_intervals();
+					let requested = app.requested_interval();
+					let show_interval_accuracy = app.settings.show_interval_accuracy;
+					drop(app);
+					if let Some(stats) = IntervalStats::new(&intervals) {
+						ui.label(RichText::new(stats.summary()).small().color(ui.style().visuals.weak_text_color()));
+
+						if let (true, Some(requested)) = (show_interval_accuracy, requested) {
+							let error = (stats.mean - requested) / requested * 100.0;
+							ui.label(
+								RichText::new(format!(
+									"requested {:.1}ms, achieved {:.1}ms ({:+.1}%)",
+									requested * 1000.0,
+									stats.mean * 1000.0,
+									error,
+								))
+								.small()
+								.color(ui.style().visuals.weak_text_color()),
+							);
+						}
+					}
 				});
 
-				if self.app().focused {
+				let app = self.app();
+				if app.settings.show_interval_graph {
+					let intervals = app.click_intervals();
+					drop(app);
+
+					ui.add_space(5.0);
+					interval_graph(ui, &intervals);
+				} else {
+					drop(app);
+				}
+
+				let app = self.app();
+				let unfocus_warning = app.focused && !app.settings.click_while_focused;
+				if app.armed
+					|| unfocus_warning
+					|| app.target_warning.is_some()
+					|| app.color_paused
+					|| app.outside_active_hours
+					|| app.other_window_in_front
+					|| app.battery_paused
+					|| app.unsafe_focus_clicking()
+				{
 					ui.with_layout(Layout::bottom_up(egui::Align::Center), |ui| {
-						warning_tag(ui, "UNFOCUS THE WINDOW TO CLICK!");
+						if app.unsafe_focus_clicking() {
+							danger_tag(ui, "CLICKS WHILE FOCUSED WITHOUT COLOR MODE!");
+						}
+						if unfocus_warning {
+							warning_tag(ui, "UNFOCUS THE WINDOW TO CLICK!");
+						}
+						if app.armed {
+							armed_tag(ui);
+						}
+						if app.color_paused {
+							warning_tag(ui, "COLOR NOT FOUND, PAUSED");
+						}
+						if app.outside_active_hours {
+							warning_tag(ui, "OUTSIDE ACTIVE HOURS");
+						}
+						if app.other_window_in_front {
+							warning_tag(ui, "WINDOW NOT ALLOWED, PAUSED");
+						}
+						if app.battery_paused {
+							warning_tag(ui, "BATTERY LOW, PAUSED");
+						}
+						if let Some(warning) = app.target_warning {
+							warning_tag(ui, warning);
+						}
 					});
 				}
 			});
     }
 
     fn compact_click_shield(&mut self, ctx: &egui::Context) {
+        let opacity = self.app().settings.shield_opacity;
         egui::CentralPanel::default()
             .frame(egui::Frame::none().inner_margin(Margin::same(10.0)))
             .show(ctx, |ui| {
@@ -363,49 +1303,71 @@ impl AppHolder {
                 ui.painter().rect_filled(
                     ui.clip_rect(),
                     Rounding::ZERO,
-                    Color32::from_black_alpha(200),
+                    Color32::from_black_alpha(opacity),
                 );
                 egui::Image::new(egui::include_image!("./assets/Click.png")).paint_at(
                     ui,
                     Rect::from_min_size(ui.clip_rect().right_center(), [50.0, 50.0].into()),
                 );
 
-                egui::Grid::new("compact_click_shield_grid").show(ui, |ui| {
-                    let app = self.app();
-
-                    ui.label("Time");
-                    ui.label(
-                        RichText::new(format!(
-                            "{:.2}",
-                            Instant::now()
-                                .duration_since(app.clicker_start_time)
-                                .as_secs_f64()
-                        ))
-                        .color(ui.style().visuals.strong_text_color()),
-                    );
-                    ui.end_row();
-
-                    ui.label("Clicks");
-                    ui.label(
-                        RichText::new(format!("{}", app.total_clicks))
+                shield_backdrop(opacity).show(ui, |ui| {
+                    egui::Grid::new("compact_click_shield_grid").show(ui, |ui| {
+                        let app = self.app();
+
+                        // There's no room for more rows, so the status takes the place of the timer
+                        if app.armed {
+                            ui.label("Status");
+                            armed_tag(ui);
+                        } else {
+                            ui.label("Time");
+                            ui.label(
+                                RichText::new(format!("{:.2}", app.session_time().as_secs_f64()))
+                                    .color(ui.style().visuals.strong_text_color()),
+                            );
+                        }
+                        ui.end_row();
+
+                        ui.label("Clicks");
+                        ui.label(
+                            RichText::new(if app.settings.accumulate_clicks {
+                                format!("{} / {}", app.session_clicks, app.total_clicks)
+                            } else {
+                                format!("{}", app.session_clicks)
+                            })
                             .color(ui.style().visuals.strong_text_color()),
-                    );
-                    ui.end_row();
+                        );
+                        ui.end_row();
+                    });
                 });
             });
     }
 
-    fn menu_bar(&mut self, ctx: &egui::Context) {
-        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
-            if ui.input_mut(|i| i.consume_shortcut(&TOGGLE_AUTO_CLICKER_SHORTCUT)) {
-                self.toggle_clicker();
-            }
-
-            ctx.input(|i| {
-                self.app_mut().focused = i.viewport().focused.unwrap();
+    /// Keeps every clicker up to date on whether the window is focused and where it is.
+    fn track_window(&self, ctx: &egui::Context) {
+        ctx.input(|i| {
+            let focused = i.viewport().focused.unwrap();
+            let window_rect = i.viewport().outer_rect.map(|rect| rect * i.pixels_per_point);
+            self.clickers.lock().unwrap().for_each(|app| {
+                if focused != app.focused {
+                    app.record_user_input();
+                }
+                if focused
+                    && !app.focused
+                    && app.clicker_enabled
+                    && !app.settings.click_while_focused
+                    && app.settings.focus_behavior == FocusBehavior::Stop
+                {
+                    app.stop_clicker(ToastLevel::Info, "Stopped when the window was focused");
+                }
+                app.focused = focused;
+                app.window_rect = window_rect;
             });
+        });
+    }
 
-            egui::menu::bar(ui, |ui| {
+    fn menu_bar(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
 				ui.menu_button("Actions", |ui| {
                     if ui
                         .add(
@@ -414,7 +1376,7 @@ impl AppHolder {
                             } else {
                                 "Start Auto Clicker"
                             })
-                            .shortcut_text(ui.ctx().format_shortcut(&TOGGLE_AUTO_CLICKER_SHORTCUT)),
+                            .shortcut_text(self.app().settings.keybinds.toggle.map_or(String::new(), |hotkey| hotkey.name())),
                         )
                         .clicked()
                     {
@@ -422,169 +1384,655 @@ impl AppHolder {
                     }
 
                     if ui
-                        .checkbox(&mut self.app_mut().compact_mode, "Compact Mode")
+                        .checkbox(&mut self.app_mut().settings.compact_mode, "Compact Mode")
                         .clicked()
                     {
-                        if self.app().compact_mode {
-                            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
-                                COMPACT_WINDOW_SIZE,
-                            ));
-                        } else {
-                            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(WINDOW_SIZE));
-                        }
+                        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window_size(&self.app().settings)));
+                    }
+
+                    if ui
+                        .checkbox(&mut self.app_mut().settings.dot_mode, "Dot Mode")
+                        .on_hover_text("Shrinks the window to a small dot that's green while the auto\nclicker runs. Click it to start or stop, drag it to move it and\nright-click it for the rest.")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.apply_window_settings(ctx);
+                    }
+
+                    let mut app = self.app_mut();
+                    let mut color_mode = app.settings.color_mode;
+                    if ui
+                        .add_enabled(
+                            !app.clicker_enabled && app.screen_available,
+                            egui::Checkbox::new(&mut color_mode, "Color Mode"),
+                        )
+                        .clicked()
+                    {
+                        app.toggle_color_mode();
+                    }
+                    drop(app);
+
+                    if ui
+                        .add_enabled(
+                            !self.app().clicker_enabled && !self.app().benchmark_running,
+                            egui::Button::new("Run Benchmark"),
+                        )
+                        .on_hover_text("Measures how many clicks per second this PC can keep up with\nusing the current settings, without actually clicking.")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.run_benchmark();
+                    }
+
+                    ui.separator();
+
+                    if ui.button("Export Settings...").clicked() {
+                        ui.close_menu();
+                        self.export_settings();
+                    }
+
+                    if ui
+                        .add_enabled(
+                            !self.app().clicker_enabled,
+                            egui::Button::new("Import Settings..."),
+                        )
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.import_settings(ctx);
                     }
                 });
 
-				if !self.app().compact_mode {
-					ui.separator();
+				self.profiles_menu(ui, ctx);
 
-					if ui.selectable_label(true, "Home").clicked() {
+				if !self.app().settings.compact_mode {
+					ui.separator();
 
+					if ui.selectable_label(self.page == Page::Home, "Home").clicked() {
+						self.page = Page::Home;
 					}
 
 					if ui.selectable_label(false, "Settings").clicked() {
 
 					}
 
-					if ui.selectable_label(false, "Keybinds").clicked() {
-
+					if ui.selectable_label(self.page == Page::Keybinds, "Keybinds").clicked() {
+						self.page = Page::Keybinds;
 					}
 				}
 
-                ui.painter().text(
+				let mut app = self.app_mut();
+				if app.settings.require_arming {
+					ui.separator();
+
+					let text = if app.master_armed {
+						RichText::new("ARMED").color(Color32::from_rgb(255, 0, 0))
+					} else {
+						RichText::new("Disarmed")
+					};
+					ui.toggle_value(&mut app.master_armed, text)
+						.on_hover_text("The auto clicker can only be started while armed.\nStopping always works.");
+				}
+				drop(app);
+
+                let version = ui.painter().text(
                     ui.available_rect_before_wrap().right_center(),
                     Align2::RIGHT_CENTER,
                     env!("CARGO_PKG_VERSION"),
                     egui::FontId::proportional(10.0),
                     ui.style().visuals.weak_text_color(),
                 );
+
+                let minute = platform::local_minute_of_day();
+                let uptime = self.launched.elapsed().as_secs();
+                ui.painter().text(
+                    version.left_center() - Vec2::new(8.0, 0.0),
+                    Align2::RIGHT_CENTER,
+                    format!(
+                        "{:02}:{:02}  up {}:{:02}:{:02}",
+                        minute / 60,
+                        minute % 60,
+                        uptime / 3600,
+                        uptime / 60 % 60,
+                        uptime % 60,
+                    ),
+                    egui::FontId::proportional(10.0),
+                    ui.style().visuals.weak_text_color(),
+                );
+                ctx.request_repaint_after(Duration::from_secs(1));
+            });
+        });
+    }
+
+    /// A slim strip along the bottom of the full window, so whether the clicker
+    /// is running and how the last session went can be seen without starting it.
+    fn status_strip(&self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("status_strip").show(ctx, |ui| {
+            let app = self.app();
+            ui.horizontal(|ui| {
+                if app.clicker_enabled {
+                    ui.label(RichText::new("Running").small().color(Color32::from_rgb(0, 200, 0)));
+                } else {
+                    ui.label(RichText::new("Stopped").small());
+                }
+
+                if app.settings.require_arming {
+                    ui.separator();
+                    if app.master_armed {
+                        ui.label(RichText::new("ARMED").small().color(Color32::from_rgb(255, 0, 0)));
+                    } else {
+                        ui.label(RichText::new("Disarmed").small());
+                    }
+                }
+
+                ui.separator();
+                let clicks = if app.clicker_enabled { "This session" } else { "Last session" };
+                ui.label(
+                    RichText::new(format!("{}: {} clicks", clicks, app.session_clicks))
+                        .small()
+                        .color(ui.style().visuals.weak_text_color()),
+                );
             });
         });
     }
 
+    /// The right-click menu on the main area, a shortcut to the common actions
+    /// in the Actions menu. It has to be added before the page so the widgets
+    /// on it are still clicked instead of the menu's background.
+    fn main_context_menu(&mut self, ui: &mut egui::Ui) {
+        let response = ui.interact(ui.max_rect(), ui.id().with("main_context_menu"), Sense::click());
+        response.context_menu(|ui| self.context_menu_items(ui));
+    }
+
+    fn context_menu_items(&mut self, ui: &mut egui::Ui) {
+        let ctx = ui.ctx().clone();
+        if ui
+            .button(if self.app().clicker_enabled {
+                "Stop Auto Clicker"
+            } else {
+                "Start Auto Clicker"
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            self.toggle_clicker();
+        }
+
+        if ui
+            .checkbox(&mut self.app_mut().settings.compact_mode, "Compact Mode")
+            .clicked()
+        {
+            ui.close_menu();
+            self.apply_window_settings(&ctx);
+        }
+
+        if ui
+            .checkbox(&mut self.app_mut().settings.dot_mode, "Dot Mode")
+            .clicked()
+        {
+            ui.close_menu();
+            self.apply_window_settings(&ctx);
+        }
+
+        ui.separator();
+
+        if ui
+            .add_enabled(!self.app().clicker_enabled, egui::Button::new("Reset Counters"))
+            .on_hover_text("Sets the click counters back to zero.")
+            .clicked()
+        {
+            ui.close_menu();
+            self.app_mut().reset_counters();
+        }
+
+        if ui.button("Copy Stats").clicked() {
+            ui.close_menu();
+            ctx.copy_text(self.app().stats_text());
+            self.app().toast(ToastLevel::Info, "Stats copied");
+        }
+    }
+
+    /// All there is of the window in Dot Mode, a dot showing whether the auto
+    /// clicker runs. Clicking it starts or stops it, dragging it moves the
+    /// borderless window and the rest is in its right-click menu.
+    fn dot_view(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none())
+            .show(ctx, |ui| {
+                let rect = Rect::from_min_size(ui.max_rect().min, DOT_WINDOW_SIZE);
+                let response = ui.interact(rect, ui.id().with("dot"), Sense::click_and_drag());
+                let color = if self.app().clicker_enabled {
+                    Color32::from_rgb(0, 200, 0)
+                } else {
+                    Color32::GRAY
+                };
+                ui.painter().rect_filled(rect.shrink(2.0), Rounding::same(rect.height() / 2.0), color);
+
+                if response.drag_started() {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                }
+                if response.clicked() {
+                    self.toggle_clicker();
+                }
+
+                // The window grows while the menu is open, it can't be drawn outside of it
+                let menu_open = response.context_menu(|ui| self.context_menu_items(ui)).is_some();
+                let size = if menu_open && self.app().settings.dot_mode {
+                    DOT_MENU_WINDOW_SIZE
+                } else if self.app().settings.dot_mode {
+                    DOT_WINDOW_SIZE
+                } else {
+                    // Dot Mode was turned off from the menu and the window was already resized
+                    return;
+                };
+                if ctx.input(|i| i.viewport().inner_rect.map(|rect| rect.size())) != Some(size) {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+                }
+            });
+    }
+
     fn click_loop(&mut self) {
         let mut app = self.app_mut();
-        app.total_clicks = 0;
-        app.mouse_is_pressed = false;
+        app.input_error = None;
+        app.target_warning = None;
+        app.session_clicks = 0;
+        app.session_ticks = 0;
+        app.session_attempts = 0;
+        app.last_click_pos = None;
+        app.physical_clicks = 0;
+        app.session_bursts = 0;
+        if !app.settings.accumulate_clicks {
+            app.total_clicks = 0;
+        }
+        // A press left behind by a failed release is let go, so every session starts with the button up
+        app.try_release_mouse();
+        app.click_times.clear();
+        app.rng = session_rng(app.settings.random_seed_enabled.then_some(app.settings.random_seed));
+        app.click_point_index = 0;
+        app.macro_index = 0;
+        app.burst_clicks = 0;
+        app.burst_button = None;
+        app.click_step_index = 0;
+        app.click_step_clicks = 0;
+        app.color_misses = 0;
+        app.color_paused = false;
+        app.outside_active_hours = false;
+        app.other_window_in_front = false;
+        app.battery_paused = false;
+        app.last_battery_check = None;
+        app.last_user_input = Instant::now();
+        app.watch_pixel_reference = None;
+        app.armed = true;
+        if app.settings.limit_mode == LimitMode::PixelChange {
+            let (x, y) = app.settings.watch_pixel;
+            app.watch_pixel_reference = screen_color(x, y);
+            if app.watch_pixel_reference.is_none() {
+                app.stop_clicker(ToastLevel::Warning, "Couldn't read the watched pixel");
+            }
+        }
+        app.template_image = None;
+        if app.settings.template_enabled {
+            let template = app
+                .settings
+                .template_path
+                .as_deref()
+                .ok_or_else(|| "No image picked".to_owned())
+                .and_then(template::load);
+            match template {
+                Ok(template) => app.template_image = Some(template),
+                Err(error) => app.stop_clicker(ToastLevel::Danger, &format!("Couldn't load the image to find: {}", error)),
+            }
+        }
         app.clicker_id += 1;
         let clicker_id = app.clicker_id;
         drop(app);
 
+        // The time the next click is due when timing against deadlines
+        let mut next_deadline: Option<Instant> = None;
+
         loop {
             let mut app = self.app_mut();
             if !app.clicker_enabled || clicker_id != app.clicker_id {
                 break;
             }
 
-            match app.limit_mode {
-                LimitMode::Clicks => {
-                    if app.total_clicks >= app.limit_mode_clicks_amount {
-                        app.clicker_enabled = false;
-                        break;
-                    }
+            match app.settings.limit_mode {
+                LimitMode::Clicks if click_limit_reached(app.session_clicks, app.settings.limit_mode_clicks_amount) => {
+                    app.stop_clicker(ToastLevel::Info, "Click limit reached");
+                    app.pending_limit_action = Some(app.settings.limit_action);
+                    break;
                 }
-                LimitMode::Time => {
-                    if Instant::now()
+                LimitMode::Time
+                    if !app.armed
+                        && Instant::now()
                         .duration_since(app.clicker_start_time)
                         .as_secs_f32()
-                        >= app.limit_mode_time
-                    {
-                        app.clicker_enabled = false;
-                        break;
+                        >= app.settings.limit_mode_time =>
+                {
+                    app.stop_clicker(ToastLevel::Info, "Time limit reached");
+                    app.pending_limit_action = Some(app.settings.limit_action);
+                    break;
+                }
+                LimitMode::PixelChange => {
+                    let (x, y) = app.settings.watch_pixel;
+                    // A failed read is not a change, the next one might work
+                    if let (Some(reference), Some(color)) = (app.watch_pixel_reference, screen_color(x, y)) {
+                        if percentage_distance_between_colors(reference, color)
+                            > app.settings.watch_pixel_threshold as f32 / 255.0
+                        {
+                            app.stop_clicker(ToastLevel::Info, "Pixel changed");
+                            app.pending_limit_action = Some(app.settings.limit_action);
+                            break;
+                        }
                     }
                 }
                 _ => {}
             }
 
-            let should_click: bool = !app.focused
-                && (!app.color_mode
-                    || (app.color_mode
-                        && percentage_distance_between_colors(
-                            app.hovering_pixel_color,
-                            app.color_mode_color,
-                        ) <= app.color_mode_distance_threshold as f32 / 255.0));
+            // Counts tries too, so a session waiting on Color Mode can't wait forever
+            app.session_attempts += 1;
+            if app.settings.attempt_limit_enabled && app.session_attempts > app.settings.attempt_limit {
+                app.stop_clicker(ToastLevel::Info, "Attempt limit reached");
+                app.pending_limit_action = Some(app.settings.limit_action);
+                break;
+            }
+
+            // The safety cap applies on top of any limit mode unless explicitly disabled
+            if app.settings.safety_cap_enabled
+                && (app.session_clicks >= app.settings.safety_cap_clicks
+                    || app.clicker_start_time.elapsed()
+                        >= Duration::from_secs(app.settings.safety_cap_minutes as u64 * 60))
+            {
+                app.stop_clicker(ToastLevel::Warning, "Safety cap reached");
+                break;
+            }
+
+            if app.settings.idle_timeout_enabled
+                && app.last_user_input.elapsed()
+                    >= Duration::from_secs(app.settings.idle_timeout_minutes as u64 * 60)
+            {
+                let reason = format!("Stopped after {} min without any input", app.settings.idle_timeout_minutes);
+                app.stop_clicker(ToastLevel::Info, &reason);
+                break;
+            }
+
+            let color_matches = !app.settings.color_mode || app.update_color_match();
+            let audio_loud = !app.settings.audio_trigger || app.audio_loud();
+            app.outside_active_hours = app.settings.active_hours_enabled && !app.in_active_hours();
+            app.other_window_in_front = !app.focused && !app.allowed_window_in_front();
+            if app.settings.battery_pause_enabled {
+                app.update_battery_pause();
+            }
+            let should_click: bool = (!app.focused || app.settings.click_while_focused)
+                && color_matches
+                && audio_loud
+                && !app.outside_active_hours
+                && !app.other_window_in_front
+                && !app.battery_paused;
 
+            let mut post_click_delay = Duration::ZERO;
+            // Whether the next click is still part of this burst and only waits the spacing
+            let mut mid_burst = false;
+            // How long the click itself took, like the press of a smart double click
+            let mut click_duration = Duration::ZERO;
             if should_click {
-                app.mouse_is_pressed = !app.mouse_is_pressed;
-                app.click_mouse();
-                app.total_clicks += 1;
+                let click_start = Instant::now();
+                let result = app.perform_action();
+                click_duration = click_start.elapsed();
+                match result {
+                    Ok(true) => {
+                        if app.armed {
+                            // The session starts with the first real click, not when the clicker was enabled
+                            app.armed = false;
+                            app.clicker_start_time = Instant::now();
+                        }
+                        app.session_clicks += 1;
+                        app.total_clicks += 1;
+                        app.record_click_time();
+                        // Wherever the click was aimed, this is where the cursor ended up clicking
+                        app.last_click_pos = app.mouse.get_position().ok().map(|point| (point.x, point.y));
+                        if app.settings.action_type != ActionType::AntiIdle {
+                            post_click_delay = Duration::from_millis(app.settings.post_click_delay_ms as u64);
+                        }
+                        if app.settings.burst_enabled {
+                            if app.advance_burst() {
+                                post_click_delay += Duration::from_secs_f32(app.settings.burst_pause);
+                                app.session_bursts += 1;
+                            } else {
+                                mid_burst = app.settings.burst_spacing_enabled;
+                            }
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(error) => {
+                        app.input_error = Some(error);
+                        app.stop_clicker(ToastLevel::Danger, "Mouse input failed");
+                        break;
+                    }
+                }
+            }
+            // Waiting for the first click isn't part of the session yet
+            if !app.armed {
+                app.session_ticks += 1;
             }
 
-            let total_seconds: f64 = app.hours as f64 * 3600.0
-                + app.minutes as f64 * 60.0
-                + app.seconds as f64
-                + app.milliseconds as f64 / 1000.0;
+            let total_seconds: f64 = app.settings.hours as f64 * 3600.0
+                + app.settings.minutes as f64 * 60.0
+                + app.settings.seconds as f64
+                + app.settings.milliseconds as f64 / 1000.0;
 
-            let time_to_wait: f64 = match app.interval_mode {
+            let time_to_wait: f64 = match app.settings.interval_mode {
                 IntervalMode::Constant => total_seconds,
                 IntervalMode::Random => {
-                    let mut rng = rand::thread_rng();
+                    let min = app.settings.interval_mode_random_min;
+                    let max = app.settings.interval_mode_random_max;
 
-                    rng.gen_range(
-                        app.interval_mode_random_min as f64..=app.interval_mode_random_max as f64,
-                    )
+                    random_interval(&mut app.rng, min, max)
                 }
             };
 
+            let time_to_wait = if app.color_paused {
+                COLOR_PAUSED_POLL_INTERVAL
+            } else if app.armed && app.settings.prime_click {
+                // Keep checking until the first click is allowed instead of waiting a whole interval
+                PRIME_CLICK_POLL_INTERVAL
+            } else if app.macro_active() {
+                // Wait as long as the recording did before the next action
+                let next = app.settings.macro_actions[app.macro_index % app.settings.macro_actions.len()];
+                next.delay_ms as f64 / 1000.0
+            } else if mid_burst {
+                // Every click is its own pass through the loop, so the limits and
+                // stopping still apply between the clicks of a burst
+                app.settings.burst_spacing_ms as f64 / 1000.0
+            } else {
+                time_to_wait
+            };
+            let time_to_wait = time_to_wait.max(app.settings.min_interval());
+            let smooth_timing = app.settings.smooth_timing
+                && app.settings.interval_mode == IntervalMode::Constant
+                && !app.macro_active()
+                && !app.color_paused;
+            let interval_from_click_start = app.settings.interval_from_click_start;
+
             drop(app);
 
-            sleep(Duration::from_secs_f64(time_to_wait));
+            sleep(post_click_delay);
+
+            let interval = Duration::from_secs_f64(time_to_wait);
+            let sleep_start = Instant::now();
+            let requested = if smooth_timing {
+                let deadline = smooth_deadline(next_deadline, sleep_start, interval, post_click_delay);
+                next_deadline = Some(deadline);
+                deadline.saturating_duration_since(sleep_start)
+            } else if interval_from_click_start {
+                next_deadline = None;
+                interval.saturating_sub(click_duration)
+            } else {
+                next_deadline = None;
+                interval
+            };
+            sleep(requested);
+
+            if let Some(suspended) = suspended_time(requested, sleep_start.elapsed()) {
+                // Don't count the time spent suspended towards the time limit, safety cap or idle timeout
+                let mut app = self.app_mut();
+                app.clicker_start_time += suspended;
+                app.last_user_input += suspended;
+                app.click_times.clear();
+                app.toast(ToastLevel::Info, "Resumed after the system was asleep");
+            }
         }
     }
 
     fn show_menu(&mut self, ui: &mut egui::Ui) {
         egui::ScrollArea::vertical().show(ui, |ui| {
-			let enabled = !self.app().clicker_enabled;
+			{
+				let mut app = self.app_mut();
+				if !app.mouse_available {
+					ui.vertical_centered(|ui| {
+						danger_tag(ui, "NO MOUSE ACCESS, CLICKING IS DISABLED!");
+					});
+					ui.add_space(5.0);
+				} else if let Some(error) = &app.input_error {
+					ui.vertical_centered(|ui| {
+						danger_tag(ui, "MOUSE INPUT FAILED, CLICKING STOPPED!");
+						ui.label(RichText::new(error).small().color(ui.style().visuals.weak_text_color()));
+					});
+					ui.add_space(5.0);
+				} else if app.clicker_enabled {
+					// Shown instead of the click shield when editing while clicking
+					ui.vertical_centered(|ui| {
+						warning_tag(ui, "AUTO CLICKER IS RUNNING!");
+						speed_buttons(ui, &mut app);
+						if app.armed {
+							armed_tag(ui);
+						}
+						if app.focused && !app.settings.click_while_focused {
+							warning_tag(ui, "UNFOCUS THE WINDOW TO CLICK!");
+						}
+						if let Some(warning) = app.target_warning {
+							warning_tag(ui, warning);
+						}
+						if app.color_paused {
+							warning_tag(ui, "COLOR NOT FOUND, PAUSED");
+						}
+						if app.outside_active_hours {
+							warning_tag(ui, "OUTSIDE ACTIVE HOURS");
+						}
+						if app.other_window_in_front {
+							warning_tag(ui, "WINDOW NOT ALLOWED, PAUSED");
+						}
+						if app.battery_paused {
+							warning_tag(ui, "BATTERY LOW, PAUSED");
+						}
+					});
+					ui.add_space(5.0);
+				}
+
+				// What the intervals of the last session actually looked like
+				if !app.clicker_enabled {
+					if let Some(stats) = IntervalStats::new(&app.click_intervals()) {
+						ui.vertical_centered(|ui| {
+							ui.label(
+								RichText::new(format!("Last session: {}", stats.summary()))
+									.small()
+									.color(ui.style().visuals.weak_text_color()),
+							);
+						});
+						ui.add_space(5.0);
+					}
+				}
+
+				if app.unsafe_focus_clicking() {
+					ui.vertical_centered(|ui| {
+						danger_tag(ui, "CLICKS WHILE FOCUSED WITHOUT COLOR MODE!");
+					});
+					ui.add_space(5.0);
+				}
+			}
+
+			let app = self.app();
+			let running = app.clicker_enabled;
+			let enabled = !running || app.settings.edit_while_clicking;
+			drop(app);
 			ui.add_enabled_ui(enabled, |ui| {
 				let mut app = self.app_mut();
 
 				egui::Frame::popup(&ui.ctx().style()).show(ui, |ui| {
+					// Only the Display settings can be changed while clicking
+					if running {
+						ui.disable();
+					}
 					big_header(ui, "Click Interval", egui::Image::new(egui::include_image!("./assets/ClickInterval.png")));
 
 					ui.vertical(|ui| {
 						if ui
-							.radio(app.interval_mode == IntervalMode::Constant, "Constant")
+							.radio(app.settings.interval_mode == IntervalMode::Constant, "Constant")
 							.clicked()
 						{
-							app.interval_mode = IntervalMode::Constant;
+							app.settings.interval_mode = IntervalMode::Constant;
 						}
 
-						ui.add_enabled_ui(app.interval_mode == IntervalMode::Constant, |ui| {
-							let mut h = app.hours;
-							let mut m = app.minutes;
-							let mut s = app.seconds;
-							let mut ms = app.milliseconds;
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Constant, |ui| {
+							let previous = app.settings.constant_interval();
+							let changed = if app.settings.interval_slider {
+								show_cps_slider(ui, &mut app.settings)
+							} else {
+								let mut h = app.settings.hours;
+								let mut m = app.settings.minutes;
+								let mut s = app.settings.seconds;
+								let mut ms = app.settings.milliseconds;
 
-							show_constant_interval_mode(ui, &mut h, &mut m, &mut s, &mut ms);
+								show_constant_interval_mode(ui, &mut h, &mut m, &mut s, &mut ms, app.settings.type_values);
 
-							app.hours = h;
-							app.minutes = m;
-							app.seconds = s;
-							app.milliseconds = ms;
+								let changed = (h, m, s, ms)
+									!= (app.settings.hours, app.settings.minutes, app.settings.seconds, app.settings.milliseconds);
+								app.settings.hours = h;
+								app.settings.minutes = m;
+								app.settings.seconds = s;
+								app.settings.milliseconds = ms;
+								changed
+							};
+
+							if app.settings.snap_to_cps {
+								if changed {
+									let snapped = snap_to_cps(app.settings.constant_interval(), previous);
+									app.settings.set_constant_interval(snapped);
+								}
+
+								let interval = app.settings.constant_interval();
+								if interval > 0.0 {
+									ui.label(
+										RichText::new(format!("{:.2} clicks per second", 1.0 / interval))
+											.small()
+											.color(ui.style().visuals.weak_text_color()),
+									);
+								}
+							}
 						});
 
 						ui.add_space(15.0);
 
 						if ui
 							.radio(
-								app.interval_mode == IntervalMode::Random,
+								app.settings.interval_mode == IntervalMode::Random,
 								"Random Interval",
 							)
 							.clicked()
 						{
-							app.interval_mode = IntervalMode::Random;
+							app.settings.interval_mode = IntervalMode::Random;
 						}
 
-						ui.add_enabled_ui(app.interval_mode == IntervalMode::Random, |ui| {
-							let mut min = app.interval_mode_random_min;
-							let mut max = app.interval_mode_random_max;
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Random, |ui| {
+							let mut min = app.settings.interval_mode_random_min;
+							let mut max = app.settings.interval_mode_random_max;
 
-							show_random_interval_mode(ui, &mut min, &mut max);
+							show_random_interval_mode(ui, &mut min, &mut max, app.settings.type_values);
 
-							app.interval_mode_random_min = min;
-							app.interval_mode_random_max = max;
+							app.settings.interval_mode_random_min = min;
+							app.settings.interval_mode_random_max = max;
 						});
-						ui.add_enabled_ui(app.interval_mode == IntervalMode::Random, |ui| {
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Random, |ui| {
 							ui.columns(2, |columns| {
 								let fields = ["Min", "Max"];
 								fields.into_iter().enumerate().for_each(|(i, text)| {
@@ -594,15 +2042,16 @@ impl AppHolder {
 						});
 					});
 
-					let total_seconds: f64 = match app.interval_mode {
+					let total_seconds: f64 = match app.settings.interval_mode {
 						IntervalMode::Constant => {
-							app.hours as f64 * 3600.0
-								+ app.minutes as f64 * 60.0
-								+ app.seconds as f64
-								+ app.milliseconds as f64 / 1000.0
+							app.settings.hours as f64 * 3600.0
+								+ app.settings.minutes as f64 * 60.0
+								+ app.settings.seconds as f64
+								+ app.settings.milliseconds as f64 / 1000.0
 						}
-						IntervalMode::Random => app.interval_mode_random_max as f64,
+						IntervalMode::Random => app.settings.interval_mode_random_max as f64,
 					};
+					let total_seconds = total_seconds.max(app.settings.min_interval());
 					let cps: u32 = (1.0 / total_seconds) as u32;
 
 
@@ -613,13 +2062,120 @@ impl AppHolder {
 							warning_tag(ui, "YOUR SYSTEM MAY SLOW DOWN!");
 						});
 					}
+
+					if app.settings.limit_mode == LimitMode::Clicks && !app.macro_active() {
+						// A random interval averages out over the session
+						let interval = match app.settings.interval_mode {
+							IntervalMode::Constant => total_seconds,
+							IntervalMode::Random => {
+								let min = (app.settings.interval_mode_random_min as f64).max(app.settings.min_interval());
+								let max = (app.settings.interval_mode_random_max as f64).max(app.settings.min_interval());
+								(min + max) / 2.0
+							}
+						};
+						let clicks = app.settings.limit_mode_clicks_amount.max(1);
+						let bursts = if app.settings.burst_enabled {
+							(clicks - 1) / app.settings.burst_size.max(1)
+						} else {
+							0
+						};
+						let waits = if app.settings.burst_enabled && app.settings.burst_spacing_enabled {
+							// Only the last click of a burst waits the interval
+							let ends = clicks.div_ceil(app.settings.burst_size.max(1));
+							let spacing = (app.settings.burst_spacing_ms as f64 / 1000.0).max(app.settings.min_interval());
+							ends as f64 * interval + (clicks - ends) as f64 * spacing
+						} else {
+							clicks as f64 * interval
+						};
+						let projected = waits
+							+ clicks as f64 * app.settings.post_click_delay_ms as f64 / 1000.0
+							+ bursts as f64 * app.settings.burst_pause as f64;
+
+						ui.vertical_centered(|ui| {
+							if projected < SHORT_SESSION_WARNING {
+								warning_tag(ui, "THE CLICK LIMIT ENDS IT ALMOST INSTANTLY!");
+							}
+							ui.label(
+								RichText::new(format!("The click limit is reached after about {:.2}s", projected))
+									.small()
+									.color(ui.style().visuals.weak_text_color()),
+							);
+						});
+					}
+
+					egui::CollapsingHeader::new("Advanced Timing").show(ui, |ui| {
+						ui.checkbox(&mut app.settings.type_values, "Type Values").on_hover_text("Shows the interval as text fields you can type exact numbers into.\nPress Enter to apply, invalid numbers are reverted.");
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Constant, |ui| {
+							ui.checkbox(&mut app.settings.interval_slider, "Clicks Per Second Slider").on_hover_text("Replaces the constant interval fields with a single slider from a\nclick every 10 seconds to 1000 clicks per second.");
+						});
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Constant, |ui| {
+							ui.horizontal(|ui| {
+								let response = ui.add(
+									egui::TextEdit::singleline(&mut app.interval_expression)
+										.hint_text("1000/15 ms")
+										.desired_width(80.0),
+								);
+								ui.label("Interval Expression").on_hover_text("Type the interval as arithmetic, like 1000/15 ms or 1/8 s, and\npress Enter to apply it. Without a unit it's in seconds.\nAnything that doesn't work out is thrown away.");
+								if response.lost_focus() {
+									if let Ok(seconds) = interval_from_expression(&app.interval_expression) {
+										app.settings.set_constant_interval(seconds);
+									}
+									app.interval_expression.clear();
+								}
+							});
+							if !app.interval_expression.trim().is_empty() {
+								// The interval fields only hold whole milliseconds
+								let preview = match interval_from_expression(&app.interval_expression) {
+									Ok(seconds) if (seconds * 1000.0).round() > 0.0 => {
+										let millis = (seconds * 1000.0).round();
+										format!("= {}ms, {:.2} clicks per second", millis, 1000.0 / millis)
+									}
+									Ok(_) => "= 0ms".to_owned(),
+									Err(error) => error,
+								};
+								ui.label(RichText::new(preview).small().color(ui.style().visuals.weak_text_color()));
+							}
+						});
+						ui.horizontal(|ui| {
+							ui.add(egui::DragValue::new(&mut app.settings.post_click_delay_ms).range(0..=10_000).suffix("ms"));
+							ui.label("Post-Click Delay").on_hover_text("An extra pause right after each click, before the interval starts.\nGives apps that need it time to register a release.");
+						});
+						ui.horizontal(|ui| {
+							ui.checkbox(&mut app.settings.max_cps_enabled, "Max Clicks Per Second").on_hover_text("Never clicks faster than this, whatever the interval says. Handy\nfor trying out random ranges without the short end running away.");
+							ui.add_enabled(
+								app.settings.max_cps_enabled,
+								egui::DragValue::new(&mut app.settings.max_cps).range(0.1..=1000.0).speed(0.1).suffix(" CPS"),
+							);
+						});
+						if let Some(cps) = app.settings.capped_cps() {
+							ui.label(
+								RichText::new(format!("Capped at {:.2} clicks per second", cps))
+									.small()
+									.color(ui.style().visuals.weak_text_color()),
+							);
+						}
+						ui.checkbox(&mut app.settings.prime_click, "Click Immediately").on_hover_text("Clicks the moment clicking is allowed after starting, like when\nthe window loses focus or the color shows up, instead of\nwaiting out the rest of the interval.");
+						let smooth_timing = app.settings.smooth_timing && app.settings.interval_mode == IntervalMode::Constant;
+						ui.add_enabled(!smooth_timing, egui::Checkbox::new(&mut app.settings.interval_from_click_start, "Interval From Click Start"))
+							.on_hover_text("Measures the interval from the start of one click to the start of\nthe next, so clicks that take a while, like a smart double click,\ndon't stretch it. Off, the interval is the gap after each click.\n\nSmooth Timing always works this way.");
+						ui.add_enabled_ui(app.settings.interval_mode == IntervalMode::Constant, |ui| {
+							ui.checkbox(&mut app.settings.snap_to_cps, "Snap To Clicks Per Second").on_hover_text("Rounds the interval to the nearest whole number of clicks per\nsecond whenever you change it, and shows the resulting rate.\n\nOnly works with a constant interval under a second.");
+							ui.checkbox(&mut app.settings.smooth_timing, "Smooth Timing").on_hover_text("Schedules every click at a fixed time instead of waiting a whole\ninterval after the last one, so small delays don't add up.\nIf the clicker falls too far behind, the missed clicks are skipped.\n\nOnly works with a constant interval.");
+						});
+					});
 				});
 
 				ui.add_space(15.0);
 
 				egui::Frame::popup(&ui.ctx().style()).show(ui, |ui| {
+					if running {
+						ui.disable();
+					}
 					big_header(ui, "Settings", egui::Image::new(egui::include_image!("./assets/Cog.png")));
 
+					ui.add(egui::TextEdit::singleline(&mut app.settings_search).hint_text("Search settings...").desired_width(f32::INFINITY));
+					let search = app.settings_search.trim().to_lowercase();
+
 					const ROW_HEIGHT: f32 = 20.0;
 					TableBuilder::new(ui)
 						.column(Column::auto().resizable(false))
@@ -627,149 +2183,1340 @@ impl AppHolder {
 						.striped(true)
 						.resizable(false)
 						.body(|mut body| {
-							body.row(ROW_HEIGHT, |mut row| {
-								row.col(|ui| {
-									setting_label(ui, "Mouse Button");
-								});
-								row.col(|ui| {
-									egui::ComboBox::from_id_source("mousebutton")
-										.selected_text(format!("{}", app.mouse_button.as_ref()))
-										.show_ui(ui, |ui| {
-											for mouse_button in MouseButton::iter() {
-												ui.selectable_value(
-													&mut app.mouse_button,
-													mouse_button,
-													mouse_button.as_ref(),
-												);
+							if matches_search(&search, &["Action"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Action").on_hover_text("What the auto clicker does every interval.\n\nAnti-Idle moves the cursor a few pixels and back instead of\nclicking, to keep the system from going idle.\n\nScroll turns the mouse wheel instead of clicking, and each\nscroll counts as a click.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											egui::ComboBox::from_id_source("actiontype")
+												.selected_text(app.settings.action_type.as_ref())
+												.show_ui(ui, |ui| {
+													for action_type in ActionType::iter() {
+														ui.selectable_value(
+															&mut app.settings.action_type,
+															action_type,
+															action_type.as_ref(),
+														);
+													}
+												});
+
+											if app.settings.action_type == ActionType::Scroll {
+												egui::ComboBox::from_id_source("scrolldirection")
+													.selected_text(app.settings.scroll_direction.as_ref())
+													.width(60.0)
+													.show_ui(ui, |ui| {
+														for direction in ScrollDirection::iter() {
+															ui.selectable_value(&mut app.settings.scroll_direction, direction, direction.as_ref());
+														}
+													});
+												ui.add(egui::DragValue::new(&mut app.settings.scroll_amount).range(1..=20).suffix(" notches"));
+											} else if app.settings.action_type.nudges() {
+												ui.add(egui::DragValue::new(&mut app.settings.anti_idle_distance).range(1..=10).suffix("px"));
 											}
 										});
+									});
 								});
-							});
-							body.row(ROW_HEIGHT, |mut row| {
-								row.col(|ui| {
-									setting_label(ui, "Click Mode");
+							}
+							if matches_search(&search, &["Mouse Button"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Mouse Button");
+									});
+									row.col(|ui| {
+										ui.add_enabled_ui(!app.settings.random_mouse_button, |ui| {
+											egui::ComboBox::from_id_source("mousebutton")
+												.selected_text(format!("{}", app.settings.mouse_button.as_ref()))
+												.show_ui(ui, |ui| {
+													for mouse_button in MouseButton::iter() {
+														ui.selectable_value(
+															&mut app.settings.mouse_button,
+															mouse_button,
+															mouse_button.as_ref(),
+														);
+													}
+												});
+										});
+									});
 								});
-								row.col(|ui| {
-									egui::ComboBox::from_id_source("clickmode")
-										.selected_text(format!("{}", app.click_mode.as_ref()))
-										.show_ui(ui, |ui| {
-											for click_mode in ClickMode::iter() {
-												ui.selectable_value(
-													&mut app.click_mode,
-													click_mode,
-													click_mode.as_ref(),
-												);
+							}
+							if matches_search(&search, &["Random Button"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Random Button").on_hover_text("If enabled, every click uses a random button picked from the\nselected ones instead of the Mouse Button setting.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.random_mouse_button, "");
+											if app.settings.random_mouse_button {
+												for mouse_button in MouseButton::iter() {
+													let mut selected = app.settings.random_mouse_buttons.contains(&mouse_button);
+													if ui.checkbox(&mut selected, mouse_button.as_ref()).changed() {
+														if selected {
+															app.settings.random_mouse_buttons.push(mouse_button);
+														} else {
+															app.settings.random_mouse_buttons.retain(|b| *b != mouse_button);
+														}
+													}
+												}
 											}
 										});
+									});
 								});
-							});
-							body.row(ROW_HEIGHT, |mut row| {
-								row.col(|ui| {
-									setting_label(ui, "Limit Mode");
-								});
-								row.col(|ui| {
-									ui.horizontal(|ui| {
-										egui::ComboBox::from_id_source("limitmode")
-											.selected_text(format!("{}", app.limit_mode.as_ref()))
+							}
+							if matches_search(&search, &["Burst"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Burst").on_hover_text("Clicks in bursts of this many clicks, with an extra pause after\neach burst on top of the interval.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.burst_enabled, "");
+											ui.add_enabled_ui(app.settings.burst_enabled, |ui| {
+												ui.add(egui::DragValue::new(&mut app.settings.burst_size).range(1..=10_000).suffix(" clicks"));
+												ui.label("then");
+												ui.add(egui::DragValue::new(&mut app.settings.burst_pause).range(0.0..=3600.0).speed(0.1).suffix("s"));
+											});
+										});
+									});
+								});
+							}
+							if app.settings.burst_enabled && matches_search(&search, &["Burst Spacing", "Burst"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Burst Spacing").on_hover_text("Clicks each burst this quickly instead of at the interval, so\nonly the time between bursts is the interval and the pause.\nStopping or reaching a limit still ends a burst right away.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.burst_spacing_enabled, "");
+											ui.add_enabled(
+												app.settings.burst_spacing_enabled,
+												egui::DragValue::new(&mut app.settings.burst_spacing_ms).range(0..=1000).suffix("ms"),
+											);
+										});
+									});
+								});
+							}
+							if app.settings.burst_enabled && app.settings.random_mouse_button && matches_search(&search, &["Button Per Burst", "Burst"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Button Per Burst").on_hover_text("Picks the random button once per burst instead of for every\nclick, so each burst clicks with one of the selected buttons.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.random_button_per_burst, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Click Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Mode");
+									});
+									row.col(|ui| {
+										egui::ComboBox::from_id_source("clickmode")
+											.selected_text(format!("{}", app.settings.click_mode.as_ref()))
 											.show_ui(ui, |ui| {
-												for limit_mode in LimitMode::iter() {
+												for click_mode in ClickMode::iter() {
 													ui.selectable_value(
-														&mut app.limit_mode,
-														limit_mode,
-														limit_mode.as_ref(),
+														&mut app.settings.click_mode,
+														click_mode,
+														click_mode.as_ref(),
 													);
 												}
 											});
-
-										match app.limit_mode {
-											LimitMode::Clicks => {
-												ui.horizontal(|ui| {
-													ui.add(
-														egui::DragValue::new(
-															&mut app.limit_mode_clicks_amount,
-														)
-														.speed(1)
-														.max_decimals(0),
-													);
-													ui.label("Clicks");
+									});
+								});
+							}
+							if matches_search(&search, &["Click Backend"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Backend").on_hover_text(
+											"How clicks are sent to the system. Try the other one if a game ignores the clicks. Both support the left, right and middle button.",
+										);
+									});
+									row.col(|ui| {
+										ui.add_enabled_ui(!app.clicker_enabled, |ui| {
+											egui::ComboBox::from_id_source("clickbackend")
+												.selected_text(app.settings.click_backend.as_ref())
+												.show_ui(ui, |ui| {
+													for backend in ClickBackendKind::iter() {
+														ui.selectable_value(
+															&mut app.settings.click_backend,
+															backend,
+															backend.as_ref(),
+														);
+													}
 												});
-											}
-											LimitMode::Time => {
-												ui.horizontal(|ui| {
-													ui.add(
-														egui::DragValue::new(
-															&mut app.limit_mode_time,
-														)
-														.speed(0.25)
-														.max_decimals(3),
-													);
-													ui.label("Seconds");
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Limit Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Limit Mode");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											egui::ComboBox::from_id_source("limitmode")
+												.selected_text(format!("{}", app.settings.limit_mode.as_ref()))
+												.show_ui(ui, |ui| {
+													for limit_mode in LimitMode::iter() {
+														ui.selectable_value(
+															&mut app.settings.limit_mode,
+															limit_mode,
+															limit_mode.as_ref(),
+														);
+													}
 												});
+
+											match app.settings.limit_mode {
+												LimitMode::Clicks => {
+													ui.horizontal(|ui| {
+														// Scale the drag speed with the value so both small and huge limits are practical
+														let speed = (app.settings.limit_mode_clicks_amount as f64 / 100.0).max(1.0);
+														ui.add(
+															egui::DragValue::new(
+																&mut app.settings.limit_mode_clicks_amount,
+															)
+															.range(1..=1_000_000)
+															.speed(speed)
+															.max_decimals(0),
+														);
+														ui.label("Clicks");
+													});
+												}
+												LimitMode::Time => {
+													ui.horizontal(|ui| {
+														ui.add(
+															egui::DragValue::new(
+																&mut app.settings.limit_mode_time,
+															)
+															.speed(0.25)
+															.max_decimals(3),
+														);
+														ui.label("Seconds");
+													});
+												}
+												_ => {}
 											}
-											_ => {}
-										}
+										});
 									});
 								});
-							});
-							body.row(ROW_HEIGHT, |mut row| {
-								row.col(|ui| {
-									ui.horizontal(|ui| {
-										setting_label(ui, "Color Mode").on_hover_text("If enabled, the auto clicker will only click if the cursor's current\nhovering pixel has the same color as the set Color property.");
-										beta_tag(ui);
-									});
-								});
-								row.col(|ui| {
-									ui.horizontal(|ui| {
-										ui.checkbox(&mut app.color_mode, "");
-										ui.add_space(-10.0);
-										if app.color_mode {
-											egui::CollapsingHeader::new("Settings").show_unindented(ui, |ui| {
-												if app.color_mode {
-													let mouse_location = autopilot::mouse::location();
-													let result = autopilot::screen::get_color(mouse_location);
-													if result.is_ok() {
-														let pixel = result.unwrap();
-														app.hovering_pixel_color =
-															Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
-													}
+							}
+							if matches_search(&search, &["Attempt Limit", "Limit Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Attempt Limit").on_hover_text("Stops after trying to click this many times, whether or not the\nclicks went through. Keeps a session held back by Color Mode or\nthe Audio Trigger from waiting forever, on top of the Limit Mode.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.attempt_limit_enabled, "");
+											ui.add_enabled(
+												app.settings.attempt_limit_enabled,
+												egui::DragValue::new(&mut app.settings.attempt_limit).range(1..=1_000_000),
+											);
+										});
+									});
+								});
+							}
+							let has_limit = app.settings.limit_mode != LimitMode::None || app.settings.attempt_limit_enabled;
+							if has_limit && matches_search(&search, &["On Limit", "Limit Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "On Limit").on_hover_text("What happens besides stopping once the limit is reached.\n\nQuit Clickr lets go of any held button before closing.");
+									});
+									row.col(|ui| {
+										egui::ComboBox::from_id_source("limitaction")
+											.selected_text(app.settings.limit_action.as_ref())
+											.show_ui(ui, |ui| {
+												for action in LimitAction::iter() {
+													ui.selectable_value(&mut app.settings.limit_action, action, action.as_ref());
 												}
-
-												ui.horizontal(|ui| {
-													ui.color_edit_button_srgba(&mut app.color_mode_color);
-													ui.label("Color").on_hover_text("The color of pixel that you need the cursor to hover over for the\nauto clicker to click.");
-												});
-												ui.horizontal(|ui| {
-													ui.add(egui::DragValue::new(&mut app.color_mode_distance_threshold).range(0u8..=255u8));
-													ui.label("Threshold").on_hover_text("This setting lets you set a threshold distance for the Color property.\n\n0.0 = Color has to be the exact same\n1.0 = Color can be any color (any distance is accepted)");
-												});
 											});
-										}
 									});
 								});
-							});
-							body.row(ROW_HEIGHT, |mut row| {
-								row.col(|ui| {
-									ui.horizontal(|ui| {
-										setting_label(ui, "Always On Top");
-										beta_tag(ui);
+							}
+							if has_limit && matches_search(&search, &["Flash Taskbar", "Limit Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Flash Taskbar").on_hover_text("Flashes Clickr in the taskbar when the limit is reached while\nthe window isn't focused, until you switch back to it.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.flash_on_limit, "");
 									});
 								});
-								row.col(|ui| {
-									if ui.checkbox(&mut app.always_on_top, "").clicked() {
-										if app.always_on_top {
-											ui.ctx().send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop))
-										} else {
-											ui.ctx().send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal))
-										}
-									}
+							}
+							if app.settings.limit_mode == LimitMode::PixelChange && matches_search(&search, &["Watched Pixel", "Limit Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Watched Pixel").on_hover_text("The color of this pixel is remembered when the auto clicker starts.\nIt stops once the color is further away than the threshold.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add(egui::DragValue::new(&mut app.settings.watch_pixel.0).prefix("x: "));
+											ui.add(egui::DragValue::new(&mut app.settings.watch_pixel.1).prefix("y: "));
+											if app.capturing_watch_pixel {
+												if ui.button("Cancel").clicked() {
+													app.capturing_watch_pixel = false;
+												}
+											} else if ui.button("Pick").clicked() {
+												app.capturing_watch_pixel = true;
+												app.capturing_fixed_position = false;
+												app.capturing_color = false;
+											}
+											ui.add(egui::DragValue::new(&mut app.settings.watch_pixel_threshold).range(0u8..=255u8))
+												.on_hover_text("Threshold\n\n0 = Any change stops\n255 = Never stops");
+											if let Some(reference) = app.watch_pixel_reference {
+												let (rect, response) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+												ui.painter().rect_filled(rect, 2.0, reference);
+												response.on_hover_text("The color at the start of the last run");
+											}
+										});
+									});
 								});
-							});
-						});
-				});
-			});
-		});
-    }
-
+							}
+							if matches_search(&search, &["Step Sequence"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Step Sequence").on_hover_text("If enabled, the auto clicker cycles through the steps below,\nclicking each step's button and mode the set amount of times\nbefore moving on to the next step.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.step_sequence_enabled, "");
+											if app.settings.step_sequence_enabled && ui.button("Add Step").clicked() {
+												let step = ClickStep {
+													mouse_button: app.settings.mouse_button,
+													click_mode: app.settings.click_mode,
+													repeat: 1,
+												};
+												app.settings.click_steps.push(step);
+											}
+										});
+									});
+								});
+							}
+							if app.settings.step_sequence_enabled {
+								let mut removed_step = None;
+								for (i, step) in app.settings.click_steps.iter_mut().enumerate() {
+									if matches_search(&search, &["Step Sequence"]) {
+										body.row(ROW_HEIGHT, |mut row| {
+											row.col(|ui| {
+												ui.label(format!("Step {}", i + 1));
+											});
+											row.col(|ui| {
+												ui.horizontal(|ui| {
+													ui.add(egui::DragValue::new(&mut step.repeat).range(1..=1000).suffix("×"));
+													egui::ComboBox::from_id_source(("stepbutton", i))
+														.width(60.0)
+														.selected_text(step.mouse_button.as_ref())
+														.show_ui(ui, |ui| {
+															for mouse_button in MouseButton::iter() {
+																ui.selectable_value(&mut step.mouse_button, mouse_button, mouse_button.as_ref());
+															}
+														});
+													egui::ComboBox::from_id_source(("stepmode", i))
+														.width(60.0)
+														.selected_text(step.click_mode.as_ref())
+														.show_ui(ui, |ui| {
+															for click_mode in ClickMode::iter() {
+																ui.selectable_value(&mut step.click_mode, click_mode, click_mode.as_ref());
+															}
+														});
+													if ui.small_button("x").clicked() {
+														removed_step = Some(i);
+													}
+												});
+											});
+										});
+									}
+								}
+								if let Some(i) = removed_step {
+									app.settings.click_steps.remove(i);
+								}
+							}
+							if matches_search(&search, &["Fixed Position"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Fixed Position").on_hover_text("If enabled, the auto clicker moves the cursor to this screen\nposition before every click.\n\nPick sets the position to wherever you click next.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.fixed_position_enabled, "");
+											if app.settings.fixed_position_enabled {
+												ui.add(egui::DragValue::new(&mut app.settings.fixed_position.0).prefix("x: "));
+												ui.add(egui::DragValue::new(&mut app.settings.fixed_position.1).prefix("y: "));
+												if app.capturing_fixed_position {
+													if ui.button("Cancel").clicked() {
+														app.capturing_fixed_position = false;
+													}
+												} else if ui.button("Pick").clicked() {
+													app.capturing_fixed_position = true;
+													app.capturing_color = false;
+													app.capturing_watch_pixel = false;
+												}
+											}
+										});
+									});
+								});
+							}
+							if app.settings.fixed_position_enabled {
+								if matches_search(&search, &["Relative To Window", "Fixed Position"]) {
+									body.row(ROW_HEIGHT, |mut row| {
+										row.col(|ui| {
+											setting_label(ui, "Relative To Window").on_hover_text("If enabled, the fixed position is stored relative to the window\nit was picked in, so it follows that window when it moves.\nClicks are skipped while the window is missing or off-screen.");
+										});
+										row.col(|ui| {
+											ui.horizontal(|ui| {
+												if ui.checkbox(&mut app.settings.fixed_position_relative, "").changed() {
+													// The stored position means something else now, so it has to be picked again
+													app.settings.fixed_position_window = None;
+												}
+												if app.settings.fixed_position_relative {
+													match &app.settings.fixed_position_window {
+														Some(title) => ui.label(RichText::new(title).small()),
+														None => ui.label(RichText::new("Pick a position").small().color(ui.style().visuals.weak_text_color())),
+													};
+												}
+											});
+										});
+									});
+								}
+								if !app.settings.fixed_position_relative && matches_search(&search, &["Monitor", "Fixed Position"]) {
+									body.row(ROW_HEIGHT, |mut row| {
+										row.col(|ui| {
+											setting_label(ui, "Monitor").on_hover_text("Stores the fixed position relative to the top left corner of a\nmonitor, so it stays on that monitor when the layout changes.");
+										});
+										row.col(|ui| {
+											let monitors = platform::monitors();
+											let monitor_label = |name: &str| {
+												match monitors.iter().position(|monitor| monitor.name == name) {
+													Some(i) => {
+														let (left, top, right, bottom) = monitors[i].rect;
+														format!("Monitor {} ({}x{})", i + 1, right - left, bottom - top)
+													}
+													None => format!("{} (missing)", name),
+												}
+											};
+
+											let selected = match &app.settings.fixed_position_monitor {
+												Some(name) => monitor_label(name),
+												None => "Whole Screen".to_owned(),
+											};
+											let mut chosen = None;
+											egui::ComboBox::from_id_source("fixedpositionmonitor")
+												.selected_text(selected)
+												.show_ui(ui, |ui| {
+													if ui.selectable_label(app.settings.fixed_position_monitor.is_none(), "Whole Screen").clicked() {
+														chosen = Some(None);
+													}
+													for monitor in &monitors {
+														let is_selected = app.settings.fixed_position_monitor.as_ref() == Some(&monitor.name);
+														if ui.selectable_label(is_selected, monitor_label(&monitor.name)).clicked() {
+															chosen = Some(Some(monitor));
+														}
+													}
+												});
+
+											// Keep the position at the same spot on the screen when switching
+											if let Some(monitor) = chosen {
+												let origin = |name: &Option<String>| {
+													name.as_ref()
+														.and_then(|name| monitors.iter().find(|monitor| &monitor.name == name))
+														.map_or((0, 0), |monitor| (monitor.rect.0, monitor.rect.1))
+												};
+												let old_origin = origin(&app.settings.fixed_position_monitor);
+												let new_origin = monitor.map_or((0, 0), |monitor| (monitor.rect.0, monitor.rect.1));
+												app.settings.fixed_position.0 += old_origin.0 - new_origin.0;
+												app.settings.fixed_position.1 += old_origin.1 - new_origin.1;
+												app.settings.fixed_position_monitor = monitor.map(|monitor| monitor.name.clone());
+											}
+										});
+									});
+								}
+								if matches_search(&search, &["Position Marker", "Fixed Position"]) {
+									body.row(ROW_HEIGHT, |mut row| {
+										row.col(|ui| {
+											setting_label(ui, "Position Marker").on_hover_text("Shows a crosshair on top of everything at the fixed position.");
+										});
+										row.col(|ui| {
+											ui.horizontal(|ui| {
+												ui.checkbox(&mut app.settings.show_fixed_position_marker, "");
+												ui.color_edit_button_srgba(&mut app.settings.fixed_position_marker_color);
+											});
+										});
+									});
+								}
+							}
+							if matches_search(&search, &["Click Region"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Region").on_hover_text("Random clicks a new random point inside the region every time.\nClick Points and Fixed Position take priority over this.");
+									});
+									row.col(|ui| {
+										egui::ComboBox::from_id_source("regionmode")
+											.selected_text(app.settings.region_mode.as_ref())
+											.show_ui(ui, |ui| {
+												for region_mode in RegionMode::iter() {
+													ui.selectable_value(
+														&mut app.settings.region_mode,
+														region_mode,
+														region_mode.as_ref(),
+													);
+												}
+											});
+									});
+								});
+							}
+							if app.settings.region_mode != RegionMode::None && matches_search(&search, &["Region Area", "Click Region"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Region Area").on_hover_text("The top left corner and size of the region, in screen pixels.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											let region = &mut app.settings.click_region;
+											let coordinates = -MAX_SCREEN_COORDINATE..=MAX_SCREEN_COORDINATE;
+											ui.add(egui::DragValue::new(&mut region.0).range(coordinates.clone()).prefix("x: "));
+											ui.add(egui::DragValue::new(&mut region.1).range(coordinates).prefix("y: "));
+											ui.add(egui::DragValue::new(&mut region.2).range(1..=MAX_SCREEN_COORDINATE - region.0).prefix("w: "));
+											ui.add(egui::DragValue::new(&mut region.3).range(1..=MAX_SCREEN_COORDINATE - region.1).prefix("h: "));
+											if ui.button("Select").on_hover_text("Drag out the region on screen").clicked() {
+												app.selecting_region = true;
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Click Points"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Points").on_hover_text("If any points are set, the auto clicker moves the cursor to each\npoint in turn before clicking instead of clicking in place.\n\nPick adds wherever you click next. Paste waits for Ctrl+V and\nreads one \"x,y\" coordinate per line from the clipboard.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.label(format!("{}", app.settings.click_points.len()));
+											if app.capturing_click_point {
+												if ui.button("Cancel").clicked() {
+													app.capturing_click_point = false;
+												}
+											} else if ui.button("Pick").clicked() {
+												app.capturing_click_point = true;
+												app.capturing_fixed_position = false;
+												app.capturing_color = false;
+												app.capturing_watch_pixel = false;
+											}
+											if app.pasting_click_points {
+												if ui.button("Cancel").clicked() {
+													app.pasting_click_points = false;
+												}
+												ui.label(RichText::new("Press Ctrl+V").small().color(ui.style().visuals.weak_text_color()));
+
+												// egui reads the clipboard itself and hands it over as a paste event
+												let pasted = ui.input(|i| {
+													i.events.iter().find_map(|event| match event {
+														egui::Event::Paste(text) => Some(text.clone()),
+														_ => None,
+													})
+												});
+												if let Some(text) = pasted {
+													app.pasting_click_points = false;
+													let (points, skipped) = parse_click_points(&text);
+													let imported = points.len();
+													app.settings.click_points.extend(points);
+
+													if skipped.is_empty() {
+														app.toast(ToastLevel::Info, format!("Imported {} click points", imported));
+													} else {
+														let lines: Vec<String> = skipped.iter().map(|line| line.to_string()).collect();
+														app.toast(
+															ToastLevel::Warning,
+															format!("Imported {} click points, skipped malformed line(s) {}", imported, lines.join(", ")),
+														);
+													}
+												}
+											} else if ui.button("Paste").clicked() {
+												app.pasting_click_points = true;
+											}
+											if ui.button("Clear").clicked() {
+												app.settings.click_points.clear();
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Click Points"]) {
+								let count = app.settings.click_points.len();
+								let (mut moved_up, mut removed_point) = (None, None);
+								for (i, point) in app.settings.click_points.iter_mut().enumerate() {
+									body.row(ROW_HEIGHT, |mut row| {
+										row.col(|ui| {
+											ui.label(format!("Point {}", i + 1));
+										});
+										row.col(|ui| {
+											ui.horizontal(|ui| {
+												ui.add(egui::DragValue::new(&mut point.0).prefix("x: "));
+												ui.add(egui::DragValue::new(&mut point.1).prefix("y: "));
+												// Moving a point down is moving the next one up
+												if ui.add_enabled(i > 0, egui::Button::new("⏶").small()).clicked() {
+													moved_up = Some(i);
+												}
+												if ui.add_enabled(i + 1 < count, egui::Button::new("⏷").small()).clicked() {
+													moved_up = Some(i + 1);
+												}
+												if ui.small_button("x").clicked() {
+													removed_point = Some(i);
+												}
+											});
+										});
+									});
+								}
+								if let Some(i) = moved_up {
+									app.settings.click_points.swap(i - 1, i);
+								}
+								if let Some(i) = removed_point {
+									app.settings.click_points.remove(i);
+								}
+							}
+							if matches_search(&search, &["Movement"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Movement").on_hover_text("How the cursor moves to Click Points, the Fixed Position and the\nClick Region before clicking. Anything but Instant takes the set\ntime per move, which slows down clicking.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											egui::ComboBox::from_id_source("movementeasing")
+												.selected_text(app.settings.movement_easing.as_ref())
+												.width(80.0)
+												.show_ui(ui, |ui| {
+													for easing in MovementEasing::iter() {
+														ui.selectable_value(&mut app.settings.movement_easing, easing, easing.as_ref());
+													}
+												});
+											if app.settings.movement_easing != MovementEasing::Instant {
+												ui.add(egui::DragValue::new(&mut app.settings.movement_duration_ms).range(0..=1000).suffix("ms"));
+												easing_preview(ui, app.settings.movement_easing);
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Cursor Offset"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Cursor Offset").on_hover_text("Clicks this many pixels away from the cursor instead of right on it,\nputting the cursor back after every click. Only used when clicking\nat the cursor, not at Click Points, the Fixed Position or a Region.\n\n0, 0 = Click right on the cursor");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add(egui::DragValue::new(&mut app.settings.cursor_offset.0).range(-1000..=1000).prefix("x: "));
+											ui.add(egui::DragValue::new(&mut app.settings.cursor_offset.1).range(-1000..=1000).prefix("y: "));
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Return Cursor"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Return Cursor").on_hover_text("Puts the cursor back where it was after clicking at Click Points,\nthe Fixed Position, the Click Region or a found image, so it can\nstill be used between clicks.");
+									});
+									row.col(|ui| {
+										ui.add(egui::Checkbox::without_text(&mut app.settings.return_cursor));
+									});
+								});
+							}
+							if matches_search(&search, &["Verify Position"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Verify Position").on_hover_text("After moving to Click Points, the Fixed Position or the Click Region,\nwait until the cursor is really there before clicking. If it doesn't\nget there in time the click is skipped.\n\nFor remote or slow connections, adds up to the timeout per click.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.verify_cursor_position, "");
+											ui.add_enabled(
+												app.settings.verify_cursor_position,
+												egui::DragValue::new(&mut app.settings.verify_cursor_timeout_ms).range(1..=1000).suffix("ms"),
+											);
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Find Image"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											setting_label(ui, "Find Image").on_hover_text("Looks for a small PNG image on the main screen every interval and\nclicks its center, taking the place of Click Points, the Fixed\nPosition and the Click Region. If it can't be found, the click\nis skipped.\n\nCrop the image from a screenshot so it looks exactly like it does\non screen, at the same size.");
+											beta_tag(ui);
+										});
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add_enabled(
+												app.screen_available && app.settings.template_path.is_some(),
+												egui::Checkbox::without_text(&mut app.settings.template_enabled),
+											);
+											let name = app
+												.settings
+												.template_path
+												.as_deref()
+												.and_then(|path| path.file_name())
+												.map_or("No image".to_owned(), |name| name.to_string_lossy().into_owned());
+											ui.label(name);
+											if ui.button("Load...").clicked() {
+												if let Some(path) = rfd::FileDialog::new().add_filter("PNG Image", &["png"]).pick_file() {
+													match template::load(&path) {
+														Ok(_) => {
+															app.settings.template_path = Some(path);
+															app.settings.template_enabled = true;
+														}
+														Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't load the image: {}", error)),
+													}
+												}
+											}
+											if !app.screen_available {
+												warning_tag(ui, "NO SCREEN ACCESS");
+											}
+										});
+									});
+								});
+							}
+							if app.settings.template_enabled && matches_search(&search, &["Match Threshold", "Find Image"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Match Threshold").on_hover_text("How alike a spot on screen has to look to the image to be clicked.\nLower it if the image isn't found, raise it if the wrong spot is\nclicked.\n\n100% = Has to look exactly the same");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add(egui::Slider::new(&mut app.settings.template_threshold, 0.5..=1.0).custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+											ui.checkbox(&mut app.settings.template_warn_missing, "Warn")
+												.on_hover_text("Shows a warning while the image can't be found.");
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Macro"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Macro").on_hover_text("Plays back a list of moves and clicks on loop instead of clicking\non an interval, waiting the recorded delay before each action.\n\nLoad reads a JSON list of actions like\n{ \"button\": \"Left\", \"x\": 100, \"y\": 200, \"delay_ms\": 50 }.\nLeave out the button to only move the cursor.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add_enabled(
+												!app.settings.macro_actions.is_empty(),
+												egui::Checkbox::without_text(&mut app.settings.macro_enabled),
+											);
+											ui.label(format!("{} actions", app.settings.macro_actions.len()));
+											if ui.button("Load...").clicked() {
+												if let Some(path) = rfd::FileDialog::new().add_filter("Clickr Macro", &["json"]).pick_file() {
+													match macros::load(&path) {
+														Ok(actions) => {
+															app.toast(ToastLevel::Info, format!("Loaded a macro with {} actions", actions.len()));
+															app.settings.macro_actions = actions;
+															app.settings.macro_enabled = true;
+														}
+														Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't load the macro: {}", error)),
+													}
+												}
+											}
+											if ui.button("Clear").clicked() {
+												app.settings.macro_actions.clear();
+												app.settings.macro_enabled = false;
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Record Macro"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Record Macro").on_hover_text("Records your clicks anywhere outside this window, with the\ntime between them, and replaces the macro with them when stopped.\n\nSave writes the macro to a file that Load can read again.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											if app.recording_macro {
+												if ui.button("Stop").clicked() {
+													app.stop_macro_recording();
+												}
+												ui.label(format!("{} recorded", app.recorded_actions.len()));
+											} else {
+												if ui.button("Record").clicked() {
+													app.start_macro_recording();
+												}
+												let save = ui.add_enabled(!app.settings.macro_actions.is_empty(), egui::Button::new("Save..."));
+												if save.clicked() {
+													if let Some(path) = rfd::FileDialog::new()
+														.add_filter("Clickr Macro", &["json"])
+														.set_file_name("Macro.json")
+														.save_file()
+													{
+														match macros::save(&path, &app.settings.macro_actions) {
+															Ok(()) => app.toast(ToastLevel::Info, "Macro saved"),
+															Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't save the macro: {}", error)),
+														}
+													}
+												}
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Color Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											setting_label(ui, "Color Mode").on_hover_text("If enabled, the auto clicker will only click if the cursor's current\nhovering pixel has the same color as the set Color property.");
+											beta_tag(ui);
+										});
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.add_enabled(app.screen_available, egui::Checkbox::without_text(&mut app.settings.color_mode));
+											if !app.screen_available {
+												warning_tag(ui, "NO SCREEN ACCESS");
+											}
+											ui.add_space(-10.0);
+											if app.settings.color_mode {
+												egui::CollapsingHeader::new("Settings").show_unindented(ui, |ui| {
+													if app.settings.color_mode {
+														let mouse_location = autopilot::mouse::location();
+														let result = autopilot::screen::get_color(mouse_location);
+														if result.is_ok() {
+															let pixel = result.unwrap();
+															app.hovering_pixel_color =
+																Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
+														}
+													}
+
+													ui.horizontal(|ui| {
+														ui.color_edit_button_srgba(&mut app.settings.color_mode_color);
+														if app.capturing_color {
+															if ui.button("Cancel").clicked() {
+																app.capturing_color = false;
+															}
+														} else if ui.button("Pick").clicked() {
+															app.capturing_color = true;
+															app.capturing_fixed_position = false;
+															app.capturing_watch_pixel = false;
+														}
+														ui.label("Color").on_hover_text("The color of pixel that you need the cursor to hover over for the\nauto clicker to click.");
+													});
+													ui.horizontal(|ui| {
+														egui::ComboBox::from_id_source("colormetric")
+															.selected_text(app.settings.color_mode_metric.as_ref())
+															.show_ui(ui, |ui| {
+																for metric in ColorMetric::iter() {
+																	ui.selectable_value(&mut app.settings.color_mode_metric, metric, metric.as_ref());
+																}
+															});
+														ui.label("Metric").on_hover_text("How colors are compared.\n\nRGB Distance = One threshold for how far apart the colors are\nHSV = Separate tolerances for hue, saturation and brightness, so\nthe hue can match while the lighting changes");
+													});
+													match app.settings.color_mode_metric {
+														ColorMetric::Rgb => {
+															ui.horizontal(|ui| {
+																ui.add(egui::DragValue::new(&mut app.settings.color_mode_distance_threshold).range(0u8..=255u8));
+																ui.label("Threshold").on_hover_text("This setting lets you set a threshold distance for the Color property.\n\n0.0 = Color has to be the exact same\n1.0 = Color can be any color (any distance is accepted)");
+															});
+														}
+														ColorMetric::Hsv => {
+															let tolerance = &mut app.settings.color_mode_hsv_tolerance;
+															ui.horizontal(|ui| {
+																ui.add(egui::DragValue::new(&mut tolerance.0).range(0..=180).suffix("°"));
+																ui.label("Hue").on_hover_text("How many degrees around the color wheel the hue can be off.\nGrays have no hue and match any.");
+															});
+															ui.horizontal(|ui| {
+																ui.add(egui::DragValue::new(&mut tolerance.1).range(0..=100).suffix("%"));
+																ui.label("Saturation").on_hover_text("How much more or less colorful the pixel can be.");
+															});
+															ui.horizontal(|ui| {
+																ui.add(egui::DragValue::new(&mut tolerance.2).range(0..=100).suffix("%"));
+																ui.label("Brightness").on_hover_text("How much brighter or darker the pixel can be.\n100% ignores lighting changes entirely.");
+															});
+														}
+													}
+													ui.horizontal(|ui| {
+														ui.add(egui::DragValue::new(&mut app.settings.color_mode_pause_after).range(0..=100_000).suffix(" misses"));
+														ui.label("Pause After").on_hover_text("Pauses clicking after the color hasn't been found this many times\nin a row, and checks less often until it shows up again.\n\n0 = Never pause");
+													});
+													ui.horizontal(|ui| {
+														let matches = app.color_matches();
+														let (led, text) = if matches {
+															(Color32::from_rgb(0, 200, 0), "Would click")
+														} else {
+															(Color32::from_rgb(255, 0, 0), "Would not click")
+														};
+														let (rect, _) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+														ui.painter().circle_filled(rect.center(), 5.0, led);
+														let (rect, _) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+														ui.painter().rect_filled(rect, 2.0, app.hovering_pixel_color);
+														ui.label(text).on_hover_text("Whether the pixel under the cursor right now matches the Color\nand Threshold above.");
+													});
+												});
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Audio Trigger"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											setting_label(ui, "Audio Trigger").on_hover_text("If enabled, the auto clicker only clicks while the sound playing on\nyour computer is louder than the threshold, like on every beat.");
+											beta_tag(ui);
+										});
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.audio_trigger, "");
+											if app.settings.audio_trigger {
+												match audio::level() {
+													Some(level) => {
+														ui.add(egui::DragValue::new(&mut app.settings.audio_threshold).range(0..=100).suffix("%"))
+															.on_hover_text("How loud the sound has to be to click.");
+														let color = if app.audio_loud() {
+															Color32::from_rgb(0, 200, 0)
+														} else {
+															ui.style().visuals.weak_text_color()
+														};
+														ui.add(egui::ProgressBar::new(level).desired_width(60.0).fill(color))
+															.on_hover_text("How loud the sound is right now.");
+													}
+													None => warning_tag(ui, "NO AUDIO OUTPUT"),
+												}
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Safety Cap"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Safety Cap").on_hover_text("A hard limit that always stops the auto clicker, no matter the\nLimit Mode, so a forgotten session can't run forever.");
+									});
+									row.col(|ui| {
+										ui.add_enabled_ui(app.settings.safety_cap_enabled, |ui| {
+											ui.horizontal(|ui| {
+												ui.add(egui::DragValue::new(&mut app.settings.safety_cap_minutes).range(1..=1440).suffix("min"));
+												let speed = (app.settings.safety_cap_clicks as f64 / 100.0).max(1.0);
+												ui.add(egui::DragValue::new(&mut app.settings.safety_cap_clicks).range(1..=100_000_000).speed(speed).suffix(" clicks"));
+											});
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Disable Safety Cap"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Disable Safety Cap");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											let mut disabled = !app.settings.safety_cap_enabled;
+											if ui.checkbox(&mut disabled, "I accept the risk").changed() {
+												app.settings.safety_cap_enabled = !disabled;
+											}
+											if disabled {
+												danger_tag(ui, "NO CAP");
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Disable Safe Mode"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Disable Safe Mode").on_hover_text("Safe mode keeps the interval at 1ms or more, so a typo can't\nfreeze your system. Disable it to click as fast as possible.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											let mut disabled = !app.settings.safe_mode;
+											if ui.checkbox(&mut disabled, "I accept the risk").changed() {
+												app.settings.safe_mode = !disabled;
+											}
+											if disabled {
+												danger_tag(ui, "NO MIN INTERVAL");
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Idle Timeout"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Idle Timeout").on_hover_text("Stops the auto clicker once you haven't pressed a key, clicked\nor switched windows for this long.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.idle_timeout_enabled, "");
+											ui.add_enabled(
+												app.settings.idle_timeout_enabled,
+												egui::DragValue::new(&mut app.settings.idle_timeout_minutes).range(1..=1440).suffix("min"),
+											);
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Active Hours"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Active Hours").on_hover_text("Only clicks between these times of day. Outside of them the auto\nclicker keeps running but waits, and picks up again once they start.\n\nAn end before the start runs past midnight.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.active_hours_enabled, "");
+											ui.add_enabled_ui(app.settings.active_hours_enabled, |ui| {
+												let settings = &mut app.settings;
+												for (i, (hour, minute)) in [&mut settings.active_hours_start, &mut settings.active_hours_end].into_iter().enumerate() {
+													if i == 1 {
+														ui.label("to");
+													}
+													ui.add(egui::DragValue::new(hour).range(0..=23).custom_formatter(|value, _| format!("{:02}", value)));
+													ui.label(":");
+													ui.add(egui::DragValue::new(minute).range(0..=59).custom_formatter(|value, _| format!("{:02}", value)));
+												}
+											});
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Control Server"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Control Server").on_hover_text("Lets scripts on this computer control the auto clicker over HTTP.\nOnly reachable from this computer.\n\nGET /status\nPOST /start, /stop or /toggle\nPOST /interval?seconds=0.1 or /interval?cps=10");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.control_server_enabled, "");
+											ui.add_enabled(
+												app.settings.control_server_enabled,
+												egui::DragValue::new(&mut app.settings.control_server_port).range(1024..=65535).prefix("Port "),
+											);
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Save On Crash"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Save On Crash").on_hover_text("If Clickr ever crashes, saves every tab's settings for the next start\nand a crash log to your config folder first. Held mouse buttons are always let go.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.save_on_crash, "");
+											if let Some(dir) = platform::config_dir() {
+												ui.label(RichText::new(dir.display().to_string()).small().color(ui.style().visuals.weak_text_color()));
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Low Battery Pause"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Low Battery Pause").on_hover_text("Pauses clicking once the battery drops below this charge while\nunplugged. With Resume, clicking picks up again when it's plugged\nin or charged back up.\n\nDoes nothing on computers without a battery.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.battery_pause_enabled, "");
+											ui.add_enabled_ui(app.settings.battery_pause_enabled, |ui| {
+												ui.add(egui::DragValue::new(&mut app.settings.battery_pause_below).range(1..=100).suffix("%"));
+												ui.checkbox(&mut app.settings.battery_resume, "Resume");
+											});
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Require Arming"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Require Arming").on_hover_text("Adds an arm switch to the menu bar. The hotkeys, controller and\nmenu can only start the auto clicker while it's armed, so it\ncan't be started by accident. Stopping always works.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.require_arming, "");
+									});
+								});
+							}
+							if matches_search(&search, &["On Focus"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "On Focus").on_hover_text("What the running auto clicker does when you focus this window.\n\nWait: stops clicking until you switch away again\nStop: turns the auto clicker off\n\nDoes nothing with Click While Focused on.");
+									});
+									row.col(|ui| {
+										ui.add_enabled_ui(!app.settings.click_while_focused, |ui| {
+											egui::ComboBox::from_id_source("focusbehavior")
+												.selected_text(app.settings.focus_behavior.as_ref())
+												.show_ui(ui, |ui| {
+													for behavior in FocusBehavior::iter() {
+														ui.selectable_value(&mut app.settings.focus_behavior, behavior, behavior.as_ref());
+													}
+												});
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Allowed Windows"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Allowed Windows").on_hover_text("If any are added, clicking pauses while a window that isn't one\nof these is in front, so it keeps going when a game's overlay or\ntooltip takes focus but not when you switch to something else.\n\nPick adds whichever window you click next. Windows are matched by title.");
+									});
+									row.col(|ui| {
+										if app.capturing_allowed_window {
+											if ui.button("Cancel").clicked() {
+												app.capturing_allowed_window = false;
+											}
+										} else if ui.button("Pick").clicked() {
+											app.capturing_allowed_window = true;
+										}
+									});
+								});
+								let mut removed = None;
+								for (i, title) in app.settings.allowed_windows.iter().enumerate() {
+									body.row(ROW_HEIGHT, |mut row| {
+										row.col(|ui| {
+											ui.label(format!("Window {}", i + 1));
+										});
+										row.col(|ui| {
+											ui.horizontal(|ui| {
+												if ui.button("Remove").clicked() {
+													removed = Some(i);
+												}
+												ui.add(egui::Label::new(title).truncate()).on_hover_text(title);
+											});
+										});
+									});
+								}
+								if let Some(i) = removed {
+									app.settings.allowed_windows.remove(i);
+								}
+							}
+							if matches_search(&search, &["Click While Focused"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click While Focused").on_hover_text("Keeps clicking while this window is focused instead of waiting\nfor you to switch away.\n\nWithout Color Mode this can click this window, so it has to be confirmed.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											let mut enabled = app.settings.click_while_focused;
+											if ui.checkbox(&mut enabled, "").changed() {
+												if enabled && !app.settings.color_mode && !app.unsafe_focus_acknowledged {
+													app.confirming_unsafe_focus = true;
+												} else {
+													app.settings.click_while_focused = enabled;
+												}
+											}
+											if app.settings.click_while_focused {
+												danger_tag(ui, "DANGEROUS");
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Ignore Own Clicks"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Ignore Own Clicks").on_hover_text("Mouse events right after one of the clickers' own clicks aren't\ncounted as your input, so the Idle Timeout can't be reset by them.\n\nTurn this off if your real clicks aren't being noticed while clicking fast.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.ignore_synthetic_input, "");
+									});
+								});
+							}
+						});
+				});
+
+				ui.add_space(15.0);
+
+				egui::Frame::popup(&ui.ctx().style()).show(ui, |ui| {
+					big_header(ui, "Display", egui::Image::new(egui::include_image!("./assets/Cog.png")));
+
+					let search = app.settings_search.trim().to_lowercase();
+
+					const ROW_HEIGHT: f32 = 20.0;
+					TableBuilder::new(ui)
+						.column(Column::auto().resizable(false))
+						.column(Column::remainder())
+						.striped(true)
+						.resizable(false)
+						.body(|mut body| {
+							if matches_search(&search, &["Random Seed"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Random Seed").on_hover_text("If enabled, the random intervals, buttons and Click Region positions\ncome out the same every time the auto clicker starts, so setups\ncan be compared run to run. Off, they're different every time.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											ui.checkbox(&mut app.settings.random_seed_enabled, "");
+											ui.add_enabled(app.settings.random_seed_enabled, egui::DragValue::new(&mut app.settings.random_seed));
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Edit While Clicking"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Edit While Clicking").on_hover_text("Instead of covering the window while the auto clicker runs,\nonly lock the settings that change how it clicks.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.edit_while_clicking, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Shield On First Click"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Shield On First Click").on_hover_text("Waits with covering the window until the auto clicker actually\nclicks, so you can still see everything while it's armed and\nwaiting, like for the window to lose focus or the color to show up.");
+									});
+									row.col(|ui| {
+										ui.add_enabled(!app.settings.edit_while_clicking, egui::Checkbox::without_text(&mut app.settings.shield_on_first_click));
+									});
+								});
+							}
+							if matches_search(&search, &["Accumulate Clicks"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Accumulate Clicks").on_hover_text("If enabled, the click counter keeps counting across start/stop\ncycles instead of resetting every time the auto clicker starts.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.accumulate_clicks, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Right-Click Menu"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Right-Click Menu").on_hover_text("Right-clicking the window opens a menu to start or stop,\ntoggle Compact Mode, reset the counters and copy the stats.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.context_menu, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Click Flash"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Flash").on_hover_text("Briefly lights up the window border when the auto clicker clicks,\nso you can see it firing from another monitor without sound.\nAt high speeds it blinks instead of flashing every click.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.click_flash, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Interval Accuracy"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Interval Accuracy").on_hover_text("Shows the interval you set next to the average one actually\nmeasured while the auto clicker runs, and how far off it is.\nHelps tell if this PC's timers can keep up with the interval.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.show_interval_accuracy, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Interval Graph"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Interval Graph").on_hover_text("Shows a graph of the time between the most recent clicks\nwhile the auto clicker is running.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.show_interval_graph, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Click Position"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Click Position").on_hover_text("Shows where the last click landed while the auto clicker is\nrunning, in screen pixels.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.show_click_position, "");
+									});
+								});
+							}
+							if matches_search(&search, &["Always On Top"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											setting_label(ui, "Always On Top");
+											beta_tag(ui);
+										});
+									});
+									row.col(|ui| {
+										if ui.checkbox(&mut app.settings.always_on_top, "").clicked() {
+											if app.settings.always_on_top {
+												ui.ctx().send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop))
+											} else {
+												ui.ctx().send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal))
+											}
+										}
+									});
+								});
+							}
+							if matches_search(&search, &["Window Corner"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Window Corner").on_hover_text("Keeps the window in a corner of the screen, a margin away from\nthe edges and the taskbar. It moves back whenever the screen\nor the window size changes.");
+									});
+									row.col(|ui| {
+										ui.horizontal(|ui| {
+											egui::ComboBox::from_id_source("windowcorner")
+												.selected_text(app.settings.window_corner.as_ref())
+												.show_ui(ui, |ui| {
+													for corner in WindowCorner::iter() {
+														ui.selectable_value(&mut app.settings.window_corner, corner, corner.as_ref());
+													}
+												});
+											if app.settings.window_corner != WindowCorner::None {
+												ui.add(egui::DragValue::new(&mut app.settings.window_corner_margin).range(0..=500).suffix("px"))
+													.on_hover_text("Distance from the edges of the screen.");
+											}
+										});
+									});
+								});
+							}
+							if matches_search(&search, &["Window Opacity"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Window Opacity").on_hover_text("Makes the window see-through, for example while streaming.");
+									});
+									row.col(|ui| {
+										ui.add(egui::Slider::new(&mut app.settings.window_opacity, 0.2..=1.0).custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+									});
+								});
+							}
+							if matches_search(&search, &["Shield Opacity"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Shield Opacity").on_hover_text("How dark the overlay covering the window while clicking is.\nLower it to see the settings behind it, the stats stay readable.");
+									});
+									row.col(|ui| {
+										ui.add(egui::Slider::new(&mut app.settings.shield_opacity, 0..=255).custom_formatter(|value, _| format!("{:.0}%", value / 2.55)));
+									});
+								});
+							}
+							if matches_search(&search, &["Show In Taskbar"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Show In Taskbar").on_hover_text("Turn off to hide the window from the taskbar and Alt+Tab.\nThe hotkeys keep working while it's hidden.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.show_in_taskbar, "");
+									});
+								});
+							}
+							if app.settings.always_on_top && matches_search(&search, &["Yield To Fullscreen", "Always On Top"]) {
+								body.row(ROW_HEIGHT, |mut row| {
+									row.col(|ui| {
+										setting_label(ui, "Yield To Fullscreen").on_hover_text("If enabled, Always On Top is paused while a fullscreen\napplication (like a game) is in the foreground.");
+									});
+									row.col(|ui| {
+										ui.checkbox(&mut app.settings.yield_to_fullscreen, "");
+									});
+								});
+							}
+						});
+				});
+			});
+		});
+    }
+
     fn show_compact_menu(&mut self, ui: &mut egui::Ui) {
 		let enabled = !self.app().clicker_enabled;
 		ui.add_enabled_ui(enabled, |ui| {
@@ -778,172 +3525,2398 @@ impl AppHolder {
 				|ui| {
 					let mut app = self.app_mut();
 
-					match app.interval_mode {
-						IntervalMode::Constant => {
-							let mut h = app.hours;
-							let mut m = app.minutes;
-							let mut s = app.seconds;
-							let mut ms = app.milliseconds;
+					match app.settings.interval_mode {
+						IntervalMode::Constant if app.settings.interval_slider => {
+							show_cps_slider(ui, &mut app.settings);
+						}
+						IntervalMode::Constant => {
+							let mut h = app.settings.hours;
+							let mut m = app.settings.minutes;
+							let mut s = app.settings.seconds;
+							let mut ms = app.settings.milliseconds;
+
+							show_constant_interval_mode(ui, &mut h, &mut m, &mut s, &mut ms, app.settings.type_values);
+
+							app.settings.hours = h;
+							app.settings.minutes = m;
+							app.settings.seconds = s;
+							app.settings.milliseconds = ms;
+						}
+						IntervalMode::Random => {
+							let mut min = app.settings.interval_mode_random_min;
+							let mut max = app.settings.interval_mode_random_max;
+
+							show_random_interval_mode(ui, &mut min, &mut max, app.settings.type_values);
+
+							app.settings.interval_mode_random_min = min;
+							app.settings.interval_mode_random_max = max;
+						}
+					}
+
+					// ui.label(
+					//     RichText::new("Compact Mode").color(ui.style().visuals.strong_text_color()),
+					// );
+				},
+			);
+		});
+    }
+
+    fn show_keybinds(&mut self, ui: &mut egui::Ui) {
+		let global_hotkeys = self.global_hotkeys;
+		// The keys other tabs listen to in the background, to point out clashes
+		let background_keybinds: Vec<Keybinds> = self
+			.clickers
+			.lock()
+			.unwrap()
+			.list
+			.iter()
+			.filter(|app| !Arc::ptr_eq(app, &self.main_app))
+			.map(|app| app.lock().unwrap().settings.keybinds.clone())
+			.filter(|keybinds| keybinds.background)
+			.collect();
+		let mut app = self.app_mut();
+
+		if let Some(action) = app.capturing_hotkey {
+			let pressed = ui.input(|i| {
+				i.events.iter().find_map(|event| match event {
+					egui::Event::Key { key, modifiers, pressed: true, repeat: false, .. } => Some(Hotkey::new(*key, *modifiers)),
+					_ => None,
+				})
+			});
+
+			match pressed {
+				Some(hotkey) if hotkey == egui::Key::Escape.into() => app.capturing_hotkey = None,
+				Some(hotkey) => match app.settings.keybinds.action_for(hotkey) {
+					Some(other) if other != action => {
+						app.toast(
+							ToastLevel::Warning,
+							format!("{} is already used by {}", hotkey.name(), other.as_ref()),
+						);
+					}
+					_ => {
+						*app.settings.keybinds.get_mut(action) = Some(hotkey);
+						app.capturing_hotkey = None;
+					}
+				},
+				None => {}
+			}
+		}
+
+		egui::Frame::popup(&ui.ctx().style()).show(ui, |ui| {
+			big_header(ui, "Keybinds", egui::Image::new(egui::include_image!("./assets/Cog.png")));
+
+			if !global_hotkeys {
+				ui.vertical_centered(|ui| {
+					warning_tag(ui, "GLOBAL HOTKEYS ARE OFF");
+					ui.label(
+						RichText::new(format!(
+							"Clickr was started with {}, so the keyboard isn't hooked and\nhotkeys only work while this window is focused. Restart without it\nto use them anywhere.",
+							NO_GLOBAL_HOTKEYS_FLAG,
+						))
+						.small()
+						.color(ui.style().visuals.weak_text_color()),
+					);
+				});
+			}
+
+			let conflicts = app.settings.keybinds.conflicts(keybinds::SENT_KEYS);
+			const ROW_HEIGHT: f32 = 20.0;
+			TableBuilder::new(ui)
+				.column(Column::auto().resizable(false))
+				.column(Column::remainder())
+				.striped(true)
+				.resizable(false)
+				.body(|mut body| {
+					for action in HotkeyAction::iter() {
+						body.row(ROW_HEIGHT, |mut row| {
+							row.col(|ui| {
+								setting_label(ui, action.as_ref());
+							});
+							row.col(|ui| {
+								ui.horizontal(|ui| {
+									let text = if app.capturing_hotkey == Some(action) {
+										RichText::new("Press a key...")
+									} else {
+										let hotkey = app.settings.keybinds.get(action);
+										let text = RichText::new(hotkey.map_or("None".to_owned(), |hotkey| hotkey.name()));
+										if conflicts.contains(&action) {
+											text.color(Color32::from_rgb(255, 0, 0))
+										} else {
+											text
+										}
+									};
+
+									if ui
+										.button(text)
+										.on_hover_text("Click, then press the key to use, holding Ctrl, Shift or Alt\nwith it to make a combination. Escape cancels.")
+										.clicked()
+									{
+										app.capturing_hotkey = Some(action);
+									}
+									if ui
+										.add_enabled(app.settings.keybinds.get(action).is_some(), egui::Button::new("Clear"))
+										.clicked()
+									{
+										*app.settings.keybinds.get_mut(action) = None;
+									}
+									if conflicts.contains(&action) {
+										danger_tag(ui, "CONFLICT");
+									}
+									let hotkey = app.settings.keybinds.get(action);
+									if hotkey.is_some()
+										&& background_keybinds.iter().any(|keybinds| {
+											HotkeyAction::iter().any(|other| other.per_clicker() && keybinds.get(other) == hotkey)
+										})
+									{
+										warning_tag(ui, "USED BY ANOTHER TAB");
+									}
+								});
+							});
+						});
+					}
+					body.row(ROW_HEIGHT, |mut row| {
+						row.col(|ui| {
+							setting_label(ui, "Burst Size").on_hover_text("How many times the Click Burst hotkey clicks per press, and the\ntime between those clicks. Uses the Mouse Button and Click Mode,\nToggle clicks once instead.");
+						});
+						row.col(|ui| {
+							ui.horizontal(|ui| {
+								ui.add(egui::DragValue::new(&mut app.settings.key_burst_clicks).range(1..=1000).suffix("×"));
+								ui.add(egui::DragValue::new(&mut app.settings.key_burst_interval_ms).range(0..=1000).suffix("ms"));
+							});
+						});
+					});
+					body.row(ROW_HEIGHT, |mut row| {
+						row.col(|ui| {
+							setting_label(ui, "Background Hotkeys").on_hover_text("This tab's Toggle, Start and Stop keys keep working while\nanother tab is selected, so each tab can have its own keys.\nThe selected tab's keys win when both use the same one.");
+						});
+						row.col(|ui| {
+							ui.checkbox(&mut app.settings.keybinds.background, "");
+						});
+					});
+					body.row(ROW_HEIGHT, |mut row| {
+						row.col(|ui| {
+							setting_label(ui, "Recenter On Stop All").on_hover_text("When Stop All Clickers is pressed, also move the cursor to the\nmiddle of the main monitor, so a clicker that was moving it\naround can't leave it stuck somewhere.");
+						});
+						row.col(|ui| {
+							ui.checkbox(&mut app.settings.stop_all_recenter, "");
+						});
+					});
+					body.row(ROW_HEIGHT, |mut row| {
+						row.col(|ui| {
+							setting_label(ui, "Gamepad").on_hover_text("Start and stop the clicker with a controller button.\n\nToggle: each press starts or stops it\nHold: it clicks while the button is held");
+						});
+						row.col(|ui| {
+							ui.horizontal(|ui| {
+								ui.checkbox(&mut app.settings.gamepad_enabled, "");
+								ui.add_enabled_ui(app.settings.gamepad_enabled, |ui| {
+									egui::ComboBox::from_id_source("gamepadbutton")
+										.selected_text(app.settings.gamepad_button.as_ref())
+										.show_ui(ui, |ui| {
+											for button in GamepadButton::iter() {
+												ui.selectable_value(&mut app.settings.gamepad_button, button, button.as_ref());
+											}
+										});
+									egui::ComboBox::from_id_source("gamepadmode")
+										.selected_text(app.settings.gamepad_mode.as_ref())
+										.show_ui(ui, |ui| {
+											for mode in GamepadMode::iter() {
+												ui.selectable_value(&mut app.settings.gamepad_mode, mode, mode.as_ref());
+											}
+										});
+								});
+							});
+						});
+					});
+					if app.settings.gamepad_mode == GamepadMode::Hold {
+						body.row(ROW_HEIGHT, |mut row| {
+							row.col(|ui| {
+								setting_label(ui, "Hold Delay").on_hover_text("How long the Hold button has to be held before the clicker starts.\nLetting go sooner clicks the Mouse Button once instead.\n\n0 = Start right away");
+							});
+							row.col(|ui| {
+								ui.add_enabled(
+									app.settings.gamepad_enabled,
+									egui::DragValue::new(&mut app.settings.hold_activation_delay_ms).range(0..=2000).suffix("ms"),
+								);
+							});
+						});
+						body.row(ROW_HEIGHT, |mut row| {
+							row.col(|ui| {
+								setting_label(ui, "On Release").on_hover_text("Runs once when the Hold button is let go, right before the clicker\nstops.\n\nOne Click: a last click with the selected Mouse Button\nRelease Button: lets go of the Mouse Button if it's still held");
+							});
+							row.col(|ui| {
+								ui.add_enabled_ui(app.settings.gamepad_enabled, |ui| {
+									egui::ComboBox::from_id_source("gamepadreleaseaction")
+										.selected_text(app.settings.gamepad_release_action.as_ref())
+										.show_ui(ui, |ui| {
+											for action in ReleaseAction::iter() {
+												ui.selectable_value(&mut app.settings.gamepad_release_action, action, action.as_ref());
+											}
+										});
+								});
+							});
+						});
+					}
+				});
+		});
+    }
+
+    fn export_settings(&mut self) {
+		let Some(path) = rfd::FileDialog::new()
+			.add_filter("Clickr Settings", &["json"])
+			.set_file_name("Clickr.json")
+			.save_file()
+		else {
+			return;
+		};
+
+		let app = self.app();
+		match app.settings.save(&path) {
+			Ok(()) => app.toast(ToastLevel::Info, "Settings exported"),
+			Err(error) => app.toast(ToastLevel::Danger, format!("Export failed: {}", error)),
+		}
+    }
+
+    fn import_settings(&mut self, ctx: &egui::Context) {
+		let Some(path) = rfd::FileDialog::new()
+			.add_filter("Clickr Settings", &["json"])
+			.pick_file()
+		else {
+			return;
+		};
+
+		match Settings::load(&path) {
+			// Only the defaults came back, which would replace every setting
+			Ok((_, Some(warning))) => {
+				self.app().toast(ToastLevel::Danger, format!("Import failed: {}", warning));
+			}
+			Ok((settings, None)) => {
+				let mut app = self.app_mut();
+				app.settings = settings;
+				app.toast(ToastLevel::Info, "Settings imported");
+				drop(app);
+				self.apply_window_settings(ctx);
+			}
+			Err(error) => {
+				self.app().toast(ToastLevel::Danger, format!("Import failed: {}", error));
+			}
+		}
+    }
+
+    /// Sends the viewport commands that match the window related settings.
+    fn apply_window_settings(&self, ctx: &egui::Context) {
+		let app = self.app();
+
+		ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window_size(&app.settings)));
+		ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(!app.settings.dot_mode));
+		// The dot is too small to find again behind other windows
+		ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(if app.settings.always_on_top || app.settings.dot_mode {
+			egui::WindowLevel::AlwaysOnTop
+		} else {
+			egui::WindowLevel::Normal
+		}));
+    }
+
+    /// Handles the hotkeys through the window's own input when the keyboard
+    /// isn't hooked, as long as no text field is being typed into.
+    fn window_hotkeys(&self, ctx: &egui::Context) {
+		if ctx.wants_keyboard_input() {
+			return;
+		}
+
+		let hotkeys: Vec<Hotkey> = ctx.input(|i| {
+			i.events
+				.iter()
+				.filter_map(|event| match event {
+					egui::Event::Key { key, modifiers, pressed: true, repeat: false, .. } => Some(Hotkey::new(*key, *modifiers)),
+					_ => None,
+				})
+				.collect()
+		});
+		let clickers = self.clickers.lock().unwrap();
+		for hotkey in hotkeys {
+			clickers.handle_hotkey(hotkey, ctx);
+		}
+    }
+
+    /// Takes the focus away from the field being edited when a clicker is about
+    /// to start. Fields only apply what was typed once they lose focus, so this
+    /// has to run before they're drawn, letting them apply it this frame before
+    /// `sync_clicker` starts clicking with the settings.
+    fn commit_pending_edits(&self, ctx: &egui::Context) {
+		let starting = self.clickers.lock().unwrap().list.iter().any(|app| {
+			let app = app.lock().unwrap();
+			app.clicker_enabled && !app.last_clicker_enabled
+		});
+		if starting {
+			ctx.memory_mut(|memory| {
+				if let Some(id) = memory.focused() {
+					memory.surrender_focus(id);
+				}
+			});
+		}
+    }
+
+    /// Applies the opacity and taskbar settings whenever they change. Retried
+    /// every frame until the window can be found, which covers startup.
+    fn update_window_style(&mut self) {
+		let app = self.app();
+		let style = (app.settings.window_opacity, app.settings.show_in_taskbar);
+		drop(app);
+
+		if style != self.window_style && platform::style_window("Clickr", style.0, style.1) {
+			self.window_style = style;
+		}
+    }
+
+    /// Moves the window into the chosen corner of its monitor's work area, but
+    /// only when the corner, margin, work area or window size changes, so the
+    /// window can still be dragged around in between.
+    fn update_window_corner(&mut self, ctx: &egui::Context) {
+		let app = self.app();
+		let (corner, margin, window_rect) =
+			(app.settings.window_corner, app.settings.window_corner_margin, app.window_rect);
+		drop(app);
+
+		if corner == WindowCorner::None {
+			self.window_anchor = None;
+			return;
+		}
+		let Some(window_rect) = window_rect else {
+			return;
+		};
+
+		let center = window_rect.center();
+		let monitors = platform::monitors();
+		let Some(monitor) = monitors
+			.iter()
+			.find(|monitor| {
+				let (left, top, right, bottom) = monitor.rect;
+				(left..right).contains(&(center.x as i32)) && (top..bottom).contains(&(center.y as i32))
+			})
+			.or(monitors.first())
+		else {
+			return;
+		};
+
+		let size = (window_rect.width().round() as i32, window_rect.height().round() as i32);
+		let anchor = (corner, margin, monitor.work_area, size);
+		if self.window_anchor == Some(anchor) {
+			return;
+		}
+		self.window_anchor = Some(anchor);
+
+		let (left, top, right, bottom) = monitor.work_area;
+		let margin = margin as i32;
+		let x = match corner {
+			WindowCorner::TopLeft | WindowCorner::BottomLeft => left + margin,
+			_ => right - margin - size.0,
+		};
+		let y = match corner {
+			WindowCorner::TopLeft | WindowCorner::TopRight => top + margin,
+			_ => bottom - margin - size.1,
+		};
+		let pixels_per_point = ctx.pixels_per_point();
+		ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(
+			[x as f32 / pixels_per_point, y as f32 / pixels_per_point].into(),
+		));
+    }
+
+    /// Asks the user to confirm clicking while focused without Color Mode before
+    /// it can be turned on or started with.
+    fn unsafe_focus_confirmation(&mut self, ctx: &egui::Context) {
+		let mut app = self.app_mut();
+		if !app.confirming_unsafe_focus {
+			return;
+		}
+
+		egui::Window::new("Click While Focused?")
+			.collapsible(false)
+			.resizable(false)
+			.anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+			.show(ctx, |ui| {
+				ui.label("Without Color Mode, the auto clicker will click this window too,\nincluding the button that stops it. Only the hotkeys can stop it then.");
+				ui.add_space(5.0);
+				ui.horizontal(|ui| {
+					if ui.button("I Understand").clicked() {
+						app.unsafe_focus_acknowledged = true;
+						app.settings.click_while_focused = true;
+						app.confirming_unsafe_focus = false;
+					}
+					if ui.button("Cancel").clicked() {
+						app.settings.click_while_focused = false;
+						app.confirming_unsafe_focus = false;
+					}
+				});
+			});
+    }
+
+    /// Lists the saved profiles to load one, saves the settings under the name
+    /// typed in, and renames or deletes the selected profile. Like the
+    /// settings, profiles can't be switched while clicking.
+    fn profiles_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+		let menu = ui.menu_button("Profiles", |ui| {
+			let enabled = !self.app().clicker_enabled;
+			ui.add_enabled_ui(enabled, |ui| {
+				let names = self.profile_names.get_or_insert_with(profiles::list).clone();
+				if names.is_empty() {
+					ui.label(RichText::new("No saved profiles").small().color(ui.style().visuals.weak_text_color()));
+				}
+				for name in &names {
+					let selected = self.app().profile.as_deref() == Some(name.as_str());
+					if ui.selectable_label(selected, name).clicked() {
+						ui.close_menu();
+						self.load_profile(name, ctx);
+					}
+				}
+
+				ui.separator();
+
+				let main_app = Arc::clone(&self.main_app);
+				let mut app = main_app.lock().unwrap();
+				ui.add(egui::TextEdit::singleline(&mut app.profile_name).hint_text("Profile name").desired_width(140.0));
+				let name = app.profile_name.trim().to_owned();
+				let selected = app.profile.clone();
+				// Saving over the loaded profile is how it's updated, any other one is asked about first
+				let replaces_other = names.iter().any(|saved| profiles::same_name(saved, &name))
+					&& !selected.as_deref().is_some_and(|selected| profiles::same_name(selected, &name));
+				if !replaces_other {
+					self.confirming_profile_overwrite = false;
+				}
+
+				let mut save = false;
+				let mut changed = false;
+				ui.horizontal(|ui| {
+					if self.confirming_profile_overwrite {
+						ui.label(format!("Replace {}?", name));
+						if ui.button("Replace").clicked() {
+							save = true;
+							self.confirming_profile_overwrite = false;
+						}
+						if ui.button("Cancel").clicked() {
+							self.confirming_profile_overwrite = false;
+						}
+						return;
+					}
+
+					if ui.add_enabled(!name.is_empty(), egui::Button::new("Save As")).clicked() {
+						if replaces_other {
+							self.confirming_profile_overwrite = true;
+						} else {
+							save = true;
+						}
+					}
+
+					let Some(selected) = selected else {
+						return;
+					};
+					let rename = ui
+						.add_enabled(!name.is_empty() && name != selected, egui::Button::new("Rename"))
+						.on_hover_text("Renames the selected profile to the name above.");
+					if rename.clicked() {
+						match profiles::rename(&selected, &name) {
+							Ok(()) => {
+								app.profile = Some(name.clone());
+								changed = true;
+							}
+							Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't rename the profile: {}", error)),
+						}
+					}
+
+					if ui
+						.button("Delete")
+						.on_hover_text("Deletes the selected profile, the current settings are kept.")
+						.clicked()
+					{
+						match profiles::delete(&selected) {
+							Ok(()) => {
+								app.toast(ToastLevel::Info, format!("Deleted profile {}", selected));
+								app.profile = None;
+								changed = true;
+							}
+							Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't delete the profile: {}", error)),
+						}
+					}
+				});
+
+				if save {
+					match profiles::save(&name, &app.settings) {
+						Ok(()) => {
+							app.toast(ToastLevel::Info, format!("Saved profile {}", name));
+							app.profile = Some(name.clone());
+							changed = true;
+						}
+						Err(error) => app.toast(ToastLevel::Danger, format!("Couldn't save the profile: {}", error)),
+					}
+				}
+				if changed {
+					self.profile_names = None;
+				}
+			});
+		});
+
+		// Read the profiles again the next time it opens, in case they changed on disk
+		if menu.inner.is_none() {
+			self.profile_names = None;
+			self.confirming_profile_overwrite = false;
+		}
+    }
+
+    /// Replaces the settings with a saved profile's, keeping the hotkeys since
+    /// they're shared by every profile.
+    fn load_profile(&mut self, name: &str, ctx: &egui::Context) {
+		match profiles::load(name) {
+			// Only the defaults came back, which would replace every setting
+			Ok((_, Some(warning))) => {
+				self.app().toast(ToastLevel::Danger, format!("Couldn't load the profile: {}", warning));
+			}
+			Ok((mut settings, None)) => {
+				let mut app = self.app_mut();
+				settings.keybinds = app.settings.keybinds.clone();
+				app.settings = settings;
+				app.profile = Some(name.to_owned());
+				app.profile_name = name.to_owned();
+				drop(app);
+				self.apply_window_settings(ctx);
+			}
+			Err(error) => {
+				self.app().toast(ToastLevel::Danger, format!("Couldn't load the profile: {}", error));
+			}
+		}
+    }
+
+    /// Shows what changed in this version, once after an update.
+    fn whats_new_popup(&mut self, ctx: &egui::Context) {
+		if !self.whats_new {
+			return;
+		}
+
+		egui::Window::new(format!("What's New in Clickr {}", env!("CARGO_PKG_VERSION")))
+			.collapsible(false)
+			.resizable(false)
+			.anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+			.show(ctx, |ui| {
+				egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+					for change in whats_new::changes() {
+						ui.label(format!("• {}", change));
+					}
+				});
+				ui.add_space(5.0);
+				if ui.button("Got It").clicked() {
+					self.whats_new = false;
+				}
+			});
+    }
+
+    /// Flashes the window border when the selected clicker clicks, so it can
+    /// be seen firing out of the corner of your eye.
+    fn click_flash(&mut self, ctx: &egui::Context) {
+		let app = self.app();
+		let (enabled, running, clicks) = (app.settings.click_flash, app.clicker_enabled, app.total_clicks);
+		drop(app);
+
+		let (last_clicks, started) = &mut self.click_flash;
+		if clicks != *last_clicks {
+			*last_clicks = clicks;
+			if enabled && running && started.is_none() {
+				*started = Some(Instant::now());
+			}
+		}
+
+		let Some(elapsed) = started.map(|started| started.elapsed()) else {
+			return;
+		};
+		if elapsed >= CLICK_FLASH_DURATION {
+			*started = None;
+			return;
+		}
+
+		let fade = 1.0 - elapsed.as_secs_f32() / CLICK_FLASH_DURATION.as_secs_f32();
+		let color = ctx.style().visuals.selection.bg_fill.gamma_multiply(fade);
+		ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("click_flash")))
+			.rect_stroke(ctx.screen_rect().shrink(1.5), Rounding::ZERO, (3.0, color));
+		ctx.request_repaint_after(CLICK_FLASH_DURATION - elapsed);
+    }
+
+    /// Minimizes or quits for the clickers that reached a limit since the last
+    /// frame. Quitting wins if clickers want different things, and lets go of
+    /// every held button first so none stays down after Clickr is gone.
+    fn perform_limit_actions(&mut self, ctx: &egui::Context) {
+		let clickers = self.clickers.lock().unwrap();
+		let mut minimize = false;
+		let mut quit = false;
+		let mut flash = false;
+		clickers.for_each(|app| {
+			let Some(action) = app.pending_limit_action.take() else {
+				return;
+			};
+			flash |= app.settings.flash_on_limit && !app.focused;
+			match action {
+				LimitAction::Minimize => minimize = true,
+				LimitAction::Quit => quit = true,
+				LimitAction::Stop => {}
+			}
+		});
+
+		if flash && !quit {
+			platform::flash_taskbar("Clickr");
+		}
+
+		if quit {
+			clickers.for_each(|app| {
+				app.clicker_enabled = false;
+				app.try_release_mouse();
+			});
+			ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+		} else if minimize {
+			ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+		}
+    }
+
+    /// Drops the window out of always-on-top while a fullscreen window is in
+    /// the foreground, and puts it back once it is gone.
+    fn update_fullscreen_yield(&mut self, ctx: &egui::Context) {
+		let mut app = self.app_mut();
+		if app.last_fullscreen_check.elapsed() < FULLSCREEN_CHECK_INTERVAL {
+			return;
+		}
+		app.last_fullscreen_check = Instant::now();
+
+		let yielding = app.settings.always_on_top
+			&& app.settings.yield_to_fullscreen
+			&& platform::foreground_window_is_fullscreen();
+
+		if yielding != app.yielding_to_fullscreen {
+			app.yielding_to_fullscreen = yielding;
+			ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+				if app.settings.always_on_top && !yielding {
+					egui::WindowLevel::AlwaysOnTop
+				} else {
+					egui::WindowLevel::Normal
+				},
+			));
+		}
+    }
+
+    fn fixed_position_marker(&mut self, ctx: &egui::Context) {
+		let app = self.app();
+		if !app.settings.fixed_position_enabled || !app.settings.show_fixed_position_marker {
+			return;
+		}
+		let Ok((x, y)) = app.resolve_fixed_position() else {
+			return;
+		};
+		let color = app.settings.fixed_position_marker_color;
+		drop(app);
+
+		// The fixed position is in screen pixels, viewports are placed in points
+		let pixels_per_point = ctx.pixels_per_point();
+		let position = egui::pos2(x as f32 / pixels_per_point, y as f32 / pixels_per_point)
+			- Vec2::splat(POSITION_MARKER_SIZE / 2.0);
+
+		ctx.show_viewport_immediate(
+			egui::ViewportId::from_hash_of("fixed_position_marker"),
+			egui::ViewportBuilder::default()
+				.with_title("Clickr Marker")
+				.with_position(position)
+				.with_inner_size([POSITION_MARKER_SIZE, POSITION_MARKER_SIZE])
+				.with_decorations(false)
+				.with_transparent(true)
+				.with_always_on_top()
+				.with_mouse_passthrough(true)
+				.with_taskbar(false)
+				.with_active(false)
+				.with_resizable(false),
+			|ctx, _class| {
+				egui::CentralPanel::default()
+					.frame(egui::Frame::none())
+					.show(ctx, |ui| {
+						let rect = ui.max_rect();
+						let stroke = egui::Stroke::new(2.0, color);
+						ui.painter().hline(rect.x_range(), rect.center().y, stroke);
+						ui.painter().vline(rect.center().x, rect.y_range(), stroke);
+						ui.painter().circle_stroke(rect.center(), POSITION_MARKER_SIZE / 4.0, stroke);
+					});
+			},
+		);
+    }
+
+    /// Covers the screen while the click region is being selected, so it can be
+    /// dragged out with the mouse. Escape cancels.
+    fn region_selector(&mut self, ctx: &egui::Context) {
+		if !self.app().selecting_region {
+			return;
+		}
+
+		// Our own window would otherwise stay on top of the overlay
+		ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal));
+
+		let screen = autopilot::screen::size();
+		let scale = autopilot::screen::scale() as f32 / ctx.pixels_per_point();
+		let size = Vec2::new(screen.width as f32 * scale, screen.height as f32 * scale);
+
+		let mut finished = false;
+		ctx.show_viewport_immediate(
+			egui::ViewportId::from_hash_of("region_selector"),
+			egui::ViewportBuilder::default()
+				.with_title("Clickr Region")
+				.with_position([0.0, 0.0])
+				.with_inner_size(size)
+				.with_decorations(false)
+				.with_transparent(true)
+				.with_always_on_top()
+				.with_taskbar(false)
+				.with_active(true)
+				.with_resizable(false),
+			|ctx, _class| {
+				if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+					finished = true;
+					return;
+				}
+
+				egui::CentralPanel::default()
+					.frame(egui::Frame::none())
+					.show(ctx, |ui| {
+						let rect = ui.max_rect();
+						let painter = ui.painter();
+						painter.rect_filled(rect, Rounding::ZERO, Color32::from_black_alpha(100));
+
+						let response = ui.interact(rect, ui.id().with("region_selector"), Sense::drag());
+						let pixels_per_point = ctx.pixels_per_point();
+						let mut app = self.app_mut();
+						if response.drag_started() {
+							app.region_drag_start = response.interact_pointer_pos();
+						}
+
+						let (Some(start), Some(current)) = (app.region_drag_start, ctx.pointer_latest_pos()) else {
+							painter.text(
+								rect.center(),
+								Align2::CENTER_CENTER,
+								"Drag to select the click region, Escape to cancel",
+								egui::FontId::proportional(16.0),
+								Color32::WHITE,
+							);
+							return;
+						};
+
+						// The overlay starts at the top left of the screen, so its points only need scaling
+						let selection = Rect::from_two_pos(start, current);
+						let region = (
+							(selection.min.x * pixels_per_point).round() as i32,
+							(selection.min.y * pixels_per_point).round() as i32,
+							((selection.width() * pixels_per_point).round() as i32).max(1),
+							((selection.height() * pixels_per_point).round() as i32).max(1),
+						);
+						painter.rect_stroke(selection, Rounding::ZERO, egui::Stroke::new(2.0, Color32::from_rgb(0, 170, 255)));
+						painter.text(
+							selection.right_bottom() + Vec2::splat(5.0),
+							Align2::LEFT_TOP,
+							format!("{} x {}", region.2, region.3),
+							egui::FontId::proportional(14.0),
+							Color32::WHITE,
+						);
+
+						if response.drag_stopped() {
+							app.settings.click_region = region;
+							finished = true;
+						}
+					});
+			},
+		);
+
+		if finished {
+			let mut app = self.app_mut();
+			app.selecting_region = false;
+			app.region_drag_start = None;
+			drop(app);
+			self.apply_window_settings(ctx);
+		}
+    }
+
+    /// Shows a zoomed in view of the pixels around the cursor next to it while
+    /// a position or color is being picked.
+    fn magnifier(&mut self, ctx: &egui::Context) {
+		let app = self.app();
+		if !(app.capturing_fixed_position || app.capturing_color || app.capturing_watch_pixel || app.capturing_click_point)
+			|| !app.screen_available
+		{
+			return;
+		}
+		drop(app);
+
+		let cursor = autopilot::mouse::location();
+		let screen = autopilot::screen::size();
+		let size = MAGNIFIER_PIXELS as f64;
+
+		// Keep the captured area on screen, the cursor just won't be centered near the edges
+		let origin = autopilot::geometry::Point::new(
+			(cursor.x.floor() - (size / 2.0).floor()).clamp(0.0, (screen.width - size).max(0.0)),
+			(cursor.y.floor() - (size / 2.0).floor()).clamp(0.0, (screen.height - size).max(0.0)),
+		);
+		let Ok(bitmap) = autopilot::bitmap::capture_screen_portion(autopilot::geometry::Rect::new(
+			origin,
+			autopilot::geometry::Size::new(size, size),
+		)) else {
+			return;
+		};
+		let center = ((cursor.x.floor() - origin.x) as f32, (cursor.y.floor() - origin.y) as f32);
+
+		// autopilot works in its own points, viewports are placed in egui points
+		let scale = autopilot::screen::scale() as f32 / ctx.pixels_per_point();
+		let position = egui::pos2(cursor.x as f32 * scale, cursor.y as f32 * scale) + Vec2::splat(20.0);
+		let side = MAGNIFIER_PIXELS as f32 * MAGNIFIER_ZOOM;
+
+		ctx.show_viewport_immediate(
+			egui::ViewportId::from_hash_of("magnifier"),
+			egui::ViewportBuilder::default()
+				.with_title("Clickr Magnifier")
+				.with_position(position)
+				.with_inner_size([side, side])
+				.with_decorations(false)
+				.with_always_on_top()
+				.with_mouse_passthrough(true)
+				.with_taskbar(false)
+				.with_active(false)
+				.with_resizable(false),
+			|ctx, _class| {
+				egui::CentralPanel::default()
+					.frame(egui::Frame::none())
+					.show(ctx, |ui| {
+						let painter = ui.painter();
+						let min = ui.max_rect().min;
+
+						for y in 0..MAGNIFIER_PIXELS {
+							for x in 0..MAGNIFIER_PIXELS {
+								let pixel = bitmap.get_pixel(autopilot::geometry::Point::new(
+									x as f64, y as f64,
+								));
+								painter.rect_filled(
+									Rect::from_min_size(
+										min + Vec2::new(x as f32, y as f32) * MAGNIFIER_ZOOM,
+										Vec2::splat(MAGNIFIER_ZOOM),
+									),
+									0.0,
+									Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]),
+								);
+							}
+						}
+
+						let cursor_pixel = Rect::from_min_size(
+							min + Vec2::new(center.0, center.1) * MAGNIFIER_ZOOM,
+							Vec2::splat(MAGNIFIER_ZOOM),
+						);
+						let crosshair = egui::Stroke::new(1.0, Color32::from_white_alpha(100));
+						painter.hline(ui.max_rect().x_range(), cursor_pixel.center().y, crosshair);
+						painter.vline(cursor_pixel.center().x, ui.max_rect().y_range(), crosshair);
+						painter.rect_stroke(cursor_pixel.expand(1.0), 0.0, egui::Stroke::new(1.0, Color32::BLACK));
+						painter.rect_stroke(cursor_pixel, 0.0, egui::Stroke::new(1.0, Color32::WHITE));
+					});
+			},
+		);
+    }
+
+    /// Runs everything a click takes except the input itself and the interval
+    /// for a moment on another thread, then reports how often it got through.
+    fn run_benchmark(&mut self) {
+        let mut app = self.app_mut();
+        app.benchmark_running = true;
+        let settings = app.settings.clone();
+        drop(app);
+
+        let app_arc = Arc::clone(&self.main_app);
+        let toasts = Arc::clone(&self.toasts);
+        thread::spawn(move || {
+            let benchmark = Mutex::new(App::new("Benchmark".to_owned(), settings, Arc::clone(&toasts)));
+
+            let start = Instant::now();
+            let mut iterations: u64 = 0;
+            while start.elapsed() < BENCHMARK_DURATION {
+                benchmark.lock().unwrap().dry_run_action();
+                iterations += 1;
+            }
+
+            let per_second = iterations as f64 / start.elapsed().as_secs_f64();
+            toasts.lock().unwrap().push(
+                ToastLevel::Info,
+                format!("Benchmark: up to about {:.0} clicks per second", per_second),
+            );
+            app_arc.lock().unwrap().benchmark_running = false;
+        });
+    }
+
+    fn start_clicker(&self, app_arc: &Arc<Mutex<App>>) {
+        let mut holder = AppHolder {
+            main_app: Arc::clone(app_arc),
+            clickers: Arc::clone(&self.clickers),
+            toasts: Arc::clone(&self.toasts),
+            page: Page::Home,
+            window_style: DEFAULT_WINDOW_STYLE,
+            global_hotkeys: self.global_hotkeys,
+            window_anchor: None,
+            whats_new: false,
+            profile_names: None,
+            confirming_profile_overwrite: false,
+            click_flash: (0, None),
+            launched: self.launched,
+        };
+        thread::spawn(move || {
+            holder.click_loop();
+        });
+    }
+
+    /// Starts or cleans up after a clicker whose enabled state changed since the last frame.
+    fn sync_clicker(&self, app_arc: &Arc<Mutex<App>>, ctx: &egui::Context) {
+        let mut app = app_arc.lock().unwrap();
+        if app.clicker_enabled == app.last_clicker_enabled {
+            return;
+        }
+        // Started by a hotkey after the fields were drawn, wait for `commit_pending_edits` next frame
+        if app.clicker_enabled && ctx.memory(|memory| memory.focused().is_some()) {
+            return;
+        }
+        app.last_clicker_enabled = app.clicker_enabled;
+
+        if app.clicker_enabled {
+            app.clicker_start_time = Instant::now();
+            drop(app);
+            self.start_clicker(app_arc);
+        } else {
+            app.try_release_mouse();
+        }
+    }
+
+    /// Shows the clicker at `index`, carrying the window settings over so the
+    /// window doesn't change when switching.
+    fn select_clicker(&mut self, clickers: &mut Clickers, index: usize) {
+        let app = self.app();
+        let (always_on_top, yield_to_fullscreen, compact_mode, dot_mode) = (
+            app.settings.always_on_top,
+            app.settings.yield_to_fullscreen,
+            app.settings.compact_mode,
+            app.settings.dot_mode,
+        );
+        let window_style = (app.settings.window_opacity, app.settings.show_in_taskbar);
+        let window_corner = (app.settings.window_corner, app.settings.window_corner_margin);
+        let keybinds = app.settings.keybinds.clone();
+        let gamepad = (
+            app.settings.gamepad_enabled,
+            app.settings.gamepad_button,
+            app.settings.gamepad_mode,
+        );
+        drop(app);
+
+        clickers.selected = index;
+        self.main_app = Arc::clone(clickers.selected());
+
+        let mut app = self.app_mut();
+        app.settings.always_on_top = always_on_top;
+        app.settings.yield_to_fullscreen = yield_to_fullscreen;
+        app.settings.compact_mode = compact_mode;
+        app.settings.dot_mode = dot_mode;
+        (app.settings.window_opacity, app.settings.show_in_taskbar) = window_style;
+        (app.settings.window_corner, app.settings.window_corner_margin) = window_corner;
+        app.settings.keybinds = keybinds;
+        (
+            app.settings.gamepad_enabled,
+            app.settings.gamepad_button,
+            app.settings.gamepad_mode,
+        ) = gamepad;
+    }
+
+    fn clicker_tabs(&mut self, ctx: &egui::Context) {
+        if self.app().settings.compact_mode {
+            return;
+        }
+
+        egui::TopBottomPanel::top("clicker_tabs").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let clickers_arc = Arc::clone(&self.clickers);
+                let mut clickers = clickers_arc.lock().unwrap();
+
+                let mut selected = None;
+                for (i, app_arc) in clickers.list.iter().enumerate() {
+                    let app = app_arc.lock().unwrap();
+                    let mut text = RichText::new(&app.name);
+                    if app.clicker_enabled {
+                        text = text.color(Color32::from_rgb(0, 200, 0));
+                    }
+                    if ui
+                        .selectable_label(i == clickers.selected, text)
+                        .on_hover_text(if app.clicker_enabled { "Running" } else { "Stopped" })
+                        .clicked()
+                    {
+                        selected = Some(i);
+                    }
+                }
+                if let Some(index) = selected {
+                    self.select_clicker(&mut clickers, index);
+                }
+
+                if ui
+                    .button("+")
+                    .on_hover_text("Add a clicker with a copy of the current settings")
+                    .clicked()
+                {
+                    let name = format!("Clicker {}", clickers.next_number);
+                    clickers.next_number += 1;
+                    let settings = self.app().settings.clone();
+                    clickers.list.push(Arc::new(Mutex::new(App::new(name, settings, Arc::clone(&self.toasts)))));
+
+                    let index = clickers.list.len() - 1;
+                    self.select_clicker(&mut clickers, index);
+                }
+
+                let can_close = clickers.list.len() > 1 && !self.app().clicker_enabled;
+                if ui
+                    .add_enabled(can_close, egui::Button::new("Close"))
+                    .on_hover_text("Remove this clicker. Stop it first.")
+                    .clicked()
+                {
+                    let index = clickers.selected;
+                    clickers.list.remove(index);
+                    let index = index.min(clickers.list.len() - 1);
+                    clickers.selected = index;
+                    self.main_app = Arc::clone(clickers.selected());
+                }
+            });
+        });
+    }
+
+    fn toggle_clicker(&mut self) {
+        self.app_mut().toggle_clicker();
+    }
+}
+
+struct App {
+    name: String,
+    mouse: Mouse,
+    mouse_available: bool,
+    screen_available: bool,
+    input_error: Option<String>,
+
+    settings: Settings,
+
+    click_step_index: usize,
+    click_step_clicks: u32,
+
+    mouse_is_pressed: bool,
+    pressed_mouse_button: MouseButton,
+
+    clicker_id: u32,
+
+    click_point_index: usize,
+    /// The macro action that runs next
+    macro_index: usize,
+    burst_clicks: u32,
+    /// The button the current burst clicks with when picking one per burst
+    burst_button: Option<MouseButton>,
+    recording_macro: bool,
+    recorded_actions: Vec<MacroAction>,
+    last_recorded_action: Instant,
+
+    capturing_fixed_position: bool,
+    capturing_allowed_window: bool,
+    capturing_color: bool,
+    /// The action waiting for a key press on the Keybinds page
+    capturing_hotkey: Option<HotkeyAction>,
+    capturing_watch_pixel: bool,
+    capturing_click_point: bool,
+    /// Whether the next Ctrl+V adds its lines to the Click Points
+    pasting_click_points: bool,
+    selecting_region: bool,
+    /// Where the drag selecting the click region started, in overlay points
+    region_drag_start: Option<egui::Pos2>,
+    /// The watched pixel's color when the clicker started
+    watch_pixel_reference: Option<Color32>,
+    /// The Find Image template, read from its file when the clicker starts
+    template_image: Option<image::GrayImage>,
+    benchmark_running: bool,
+    /// Whether the Click Burst hotkey's clicks are still being sent
+    key_burst_running: bool,
+    /// Whether the running Click Burst should stop before its next click
+    key_burst_cancelled: bool,
+    target_warning: Option<&'static str>,
+
+    hovering_pixel_color: Color32,
+    color_misses: u32,
+    color_paused: bool,
+    /// Waiting for the Active Hours to start again
+    outside_active_hours: bool,
+    /// Waiting for one of the allowed windows to come back to the front
+    other_window_in_front: bool,
+    battery_paused: bool,
+    last_battery_check: Option<Instant>,
+    /// Set by the click loop when a limit is reached, for the UI to carry out
+    pending_limit_action: Option<LimitAction>,
+
+    clicker_enabled: bool,
+    last_clicker_enabled: bool,
+    clicker_start_time: Instant,
+    /// Enabled but hasn't clicked yet, e.g. because the window is still focused
+    armed: bool,
+    /// Actions that went through, one per interval at most
+    session_clicks: u32,
+    total_clicks: u32,
+    /// Times the click loop came around, whether or not it clicked
+    session_ticks: u32,
+    /// Like `session_ticks`, but also counting while waiting for the first click
+    session_attempts: u32,
+    /// Times a mouse button actually went down, two for every double click
+    physical_clicks: u32,
+    session_bursts: u32,
+    /// Where the cursor was for the most recent click, in screen pixels
+    last_click_pos: Option<(i32, i32)>,
+    click_times: VecDeque<Instant>,
+    /// Every random choice the clicker makes, seeded when it starts
+    rng: StdRng,
+
+    focused: bool,
+    /// Filters the rows of the settings tables
+    settings_search: String,
+    /// What's typed into the interval expression field, until it's applied
+    interval_expression: String,
+    /// The profile that was last loaded or saved
+    profile: Option<String>,
+    /// What's typed into the profile name field in the Profiles menu
+    profile_name: String,
+    /// The master switch that has to be on to start clicking when Require Arming is on
+    master_armed: bool,
+    /// Whether Click While Focused without Color Mode was confirmed this session
+    unsafe_focus_acknowledged: bool,
+    confirming_unsafe_focus: bool,
+    /// The window's outer rectangle in screen pixels
+    window_rect: Option<Rect>,
+    last_user_input: Instant,
+    toasts: Arc<Mutex<Toasts>>,
+    last_fullscreen_check: Instant,
+    yielding_to_fullscreen: bool,
+}
+
+impl App {
+    fn new(name: String, settings: Settings, toasts: Arc<Mutex<Toasts>>) -> App {
+        let mouse = Mouse::new();
+        let mouse_available = mouse.get_position().is_ok();
+        let screen_available = autopilot::screen::get_color(autopilot::mouse::location()).is_ok();
+
+        App {
+            name,
+            mouse,
+            mouse_available,
+            screen_available,
+            input_error: None,
+
+            settings,
+
+            click_step_index: 0,
+            click_step_clicks: 0,
+
+            mouse_is_pressed: false,
+            pressed_mouse_button: MouseButton::Left,
+
+            clicker_id: 0,
+
+            click_point_index: 0,
+            macro_index: 0,
+            burst_clicks: 0,
+            burst_button: None,
+            recording_macro: false,
+            recorded_actions: Vec::new(),
+            last_recorded_action: Instant::now(),
+
+            capturing_fixed_position: false,
+            capturing_allowed_window: false,
+            capturing_color: false,
+            capturing_hotkey: None,
+            capturing_watch_pixel: false,
+            capturing_click_point: false,
+            pasting_click_points: false,
+            selecting_region: false,
+            region_drag_start: None,
+            watch_pixel_reference: None,
+            template_image: None,
+            benchmark_running: false,
+            key_burst_running: false,
+            key_burst_cancelled: false,
+            target_warning: None,
+
+            hovering_pixel_color: Color32::BLACK,
+            color_misses: 0,
+            color_paused: false,
+            outside_active_hours: false,
+            other_window_in_front: false,
+            battery_paused: false,
+            last_battery_check: None,
+            pending_limit_action: None,
+
+            clicker_enabled: false,
+            last_clicker_enabled: false,
+            clicker_start_time: Instant::now(),
+            armed: false,
+            session_clicks: 0,
+            total_clicks: 0,
+            session_ticks: 0,
+            session_attempts: 0,
+            physical_clicks: 0,
+            session_bursts: 0,
+            last_click_pos: None,
+            click_times: VecDeque::with_capacity(CLICK_HISTORY_SIZE),
+            rng: StdRng::from_entropy(),
+
+            focused: true,
+            settings_search: String::new(),
+            interval_expression: String::new(),
+            profile: None,
+            profile_name: String::new(),
+            master_armed: false,
+            unsafe_focus_acknowledged: false,
+            confirming_unsafe_focus: false,
+            window_rect: None,
+            last_user_input: Instant::now(),
+            toasts,
+            last_fullscreen_check: Instant::now(),
+            yielding_to_fullscreen: false,
+        }
+    }
+
+    fn toast(&self, level: ToastLevel, text: impl Into<String>) {
+        self.toasts.lock().unwrap().push(level, text);
+    }
+
+    fn record_click_time(&mut self) {
+        if self.click_times.len() == CLICK_HISTORY_SIZE {
+            self.click_times.pop_front();
+        }
+        self.click_times.push_back(Instant::now());
+    }
+
+    /// The average time between clicks the settings ask for, in seconds, to
+    /// compare the measured intervals against. Nothing for macros and bursts,
+    /// whose pauses are uneven on purpose, or a zero interval.
+    fn requested_interval(&self) -> Option<f64> {
+        if self.macro_active() || self.settings.burst_enabled {
+            return None;
+        }
+
+        let min = self.settings.min_interval();
+        let interval = match self.settings.interval_mode {
+            IntervalMode::Constant => self.settings.constant_interval().max(min),
+            IntervalMode::Random => {
+                let low = (self.settings.interval_mode_random_min as f64).max(min);
+                let high = (self.settings.interval_mode_random_max as f64).max(min);
+                (low + high) / 2.0
+            }
+        };
+        let interval = interval + self.settings.post_click_delay_ms as f64 / 1000.0;
+
+        (interval > 0.0).then_some(interval)
+    }
+
+    /// Seconds between each of the recent clicks, oldest first.
+    fn click_intervals(&self) -> Vec<f64> {
+        self.click_times
+            .iter()
+            .zip(self.click_times.iter().skip(1))
+            .map(|(a, b)| b.duration_since(*a).as_secs_f64())
+            .collect()
+    }
+
+    fn record_user_input(&mut self) {
+        self.last_user_input = Instant::now();
+    }
+
+    /// Like `record_user_input`, but ignores the events our own clicks cause.
+    fn record_mouse_input(&mut self) {
+        if !(self.settings.ignore_synthetic_input && synthetic::is_recent()) {
+            self.record_user_input();
+        }
+    }
+
+    /// Adds a real click to the macro being recorded. Our own clicks and
+    /// clicks on the window, like the one stopping the recording, are left out.
+    fn record_macro_click(&mut self, button: MouseButton) {
+        if !self.recording_macro || synthetic::is_recent() {
+            return;
+        }
+        let Ok(point) = self.mouse.get_position() else {
+            return;
+        };
+        if self
+            .window_rect
+            .is_some_and(|rect| rect.contains(egui::pos2(point.x as f32, point.y as f32)))
+        {
+            return;
+        }
+
+        let delay = self.last_recorded_action.elapsed().as_millis().min(macros::MAX_DELAY_MS as u128);
+        self.last_recorded_action = Instant::now();
+        self.recorded_actions.push(MacroAction {
+            button: Some(button),
+            x: point.x,
+            y: point.y,
+            delay_ms: delay as u32,
+        });
+    }
+
+    fn start_macro_recording(&mut self) {
+        self.recorded_actions.clear();
+        self.last_recorded_action = Instant::now();
+        self.recording_macro = true;
+    }
+
+    /// Stops recording and replaces the macro with the recording, unless nothing was recorded.
+    fn stop_macro_recording(&mut self) {
+        self.recording_macro = false;
+        if self.recorded_actions.is_empty() {
+            self.toast(ToastLevel::Warning, "No clicks were recorded");
+            return;
+        }
+
+        self.settings.macro_actions = std::mem::take(&mut self.recorded_actions);
+        self.settings.macro_enabled = true;
+        let text = format!("Recorded a macro with {} actions", self.settings.macro_actions.len());
+        self.toast(ToastLevel::Info, text);
+    }
+
+    /// Checks the battery every so often, pausing below the threshold unless
+    /// plugged in and resuming once it's plugged in or charged back up, if
+    /// that's turned on.
+    fn update_battery_pause(&mut self) {
+        if self.last_battery_check.is_some_and(|last| last.elapsed() < BATTERY_CHECK_INTERVAL) {
+            return;
+        }
+        self.last_battery_check = Some(Instant::now());
+
+        let Some(battery) = platform::battery() else {
+            return;
+        };
+        let low = battery.percent < self.settings.battery_pause_below && !battery.charging;
+
+        if low && !self.battery_paused {
+            self.battery_paused = true;
+            let text = format!("{}: Paused, the battery is at {}%", self.name, battery.percent);
+            self.toast(ToastLevel::Warning, text);
+        } else if !low && self.battery_paused && self.settings.battery_resume {
+            self.battery_paused = false;
+            self.toast(ToastLevel::Info, format!("{}: Resumed, the battery is fine again", self.name));
+        }
+    }
+
+    /// Whether the local time is within the Active Hours.
+    fn in_active_hours(&self) -> bool {
+        let (start_hour, start_minute) = self.settings.active_hours_start;
+        let (end_hour, end_minute) = self.settings.active_hours_end;
+
+        in_time_window(
+            platform::local_minute_of_day(),
+            start_hour * 60 + start_minute,
+            end_hour * 60 + end_minute,
+        )
+    }
+
+    /// Whether the sound output is at least as loud as the Audio Trigger threshold.
+    fn audio_loud(&self) -> bool {
+        audio::level().is_some_and(|level| level * 100.0 >= self.settings.audio_threshold as f32)
+    }
+
+    /// Whether the hovered pixel is close enough to the Color Mode color to click.
+    fn color_matches(&self) -> bool {
+        match self.settings.color_mode_metric {
+            ColorMetric::Rgb => {
+                percentage_distance_between_colors(self.hovering_pixel_color, self.settings.color_mode_color)
+                    <= self.settings.color_mode_distance_threshold as f32 / 255.0
+            }
+            ColorMetric::Hsv => hsv_colors_match(
+                self.hovering_pixel_color,
+                self.settings.color_mode_color,
+                self.settings.color_mode_hsv_tolerance,
+            ),
+        }
+    }
+
+    /// Reads the pixel under the cursor and checks it against the Color Mode
+    /// color, pausing after too many misses in a row and resuming on the next match.
+    fn update_color_match(&mut self) -> bool {
+        let matches = match autopilot::screen::get_color(autopilot::mouse::location()) {
+            Ok(pixel) => {
+                self.hovering_pixel_color = Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
+                self.color_matches()
+            }
+            Err(_) => false,
+        };
+
+        if matches {
+            self.color_misses = 0;
+            if self.color_paused {
+                self.color_paused = false;
+                self.toast(ToastLevel::Info, format!("{}: Color found, resumed", self.name));
+            }
+        } else {
+            self.color_misses = self.color_misses.saturating_add(1);
+            let limit = self.settings.color_mode_pause_after;
+            if limit > 0 && self.color_misses >= limit && !self.color_paused {
+                self.color_paused = true;
+                self.toast(ToastLevel::Warning, format!("{}: Color not found, paused", self.name));
+            }
+        }
+
+        matches
+    }
+
+    fn pick_mouse_button(&mut self) -> MouseButton {
+        if !self.settings.random_mouse_button {
+            return self.settings.mouse_button;
+        }
+        if let (true, Some(button)) = (self.random_button_per_burst(), self.burst_button) {
+            return button;
+        }
+
+        let button = random_mouse_button(&mut self.rng, &self.settings.random_mouse_buttons, self.settings.mouse_button);
+        if self.random_button_per_burst() {
+            self.burst_button = Some(button);
+        }
+        button
+    }
+
+    fn random_button_per_burst(&self) -> bool {
+        self.settings.burst_enabled && self.settings.random_button_per_burst
+    }
+
+    /// Counts a click towards the current burst. Returns `true` if it was the
+    /// last one, after which the next burst starts with a new random button.
+    fn advance_burst(&mut self) -> bool {
+        self.burst_clicks += 1;
+        if self.burst_clicks < self.settings.burst_size {
+            return false;
+        }
+
+        self.burst_clicks = 0;
+        self.burst_button = None;
+        true
+    }
+
+    fn next_click_target(&mut self) -> ClickTarget {
+        if let Some(template) = &self.template_image {
+            if !self.screen_available {
+                return ClickTarget::Unavailable("NO SCREEN ACCESS");
+            }
+            let Some(screen) = template::capture_screen() else {
+                return ClickTarget::Unavailable("COULDN'T CAPTURE THE SCREEN");
+            };
+            return match template::find(&screen, template, self.settings.template_threshold) {
+                Some((x, y)) => ClickTarget::Position(x as i32, y as i32),
+                None if self.settings.template_warn_missing => ClickTarget::Unavailable("IMAGE NOT FOUND ON SCREEN"),
+                None => ClickTarget::Skip,
+            };
+        }
+
+        if !self.settings.click_points.is_empty() {
+            let (x, y) = self.settings.click_points[self.click_point_index % self.settings.click_points.len()];
+            self.click_point_index = (self.click_point_index + 1) % self.settings.click_points.len();
+            return ClickTarget::Position(x, y);
+        }
+
+        if self.settings.fixed_position_enabled {
+            return match self.resolve_fixed_position() {
+                Ok((x, y)) => ClickTarget::Position(x, y),
+                Err(reason) => ClickTarget::Unavailable(reason),
+            };
+        }
+
+        if self.settings.region_mode == RegionMode::Random {
+            let (x, y, width, height) = self.settings.click_region;
+            return ClickTarget::Position(
+                x.saturating_add(self.rng.gen_range(0..width.max(1))),
+                y.saturating_add(self.rng.gen_range(0..height.max(1))),
+            );
+        }
+
+        ClickTarget::Cursor
+    }
+
+    /// Turns the fixed position into a screen position, checking that it can
+    /// actually be clicked when it is relative to a window.
+    fn resolve_fixed_position(&self) -> Result<(i32, i32), &'static str> {
+        let (x, y) = self.settings.fixed_position;
+        if !self.settings.fixed_position_relative {
+            let Some(name) = &self.settings.fixed_position_monitor else {
+                return Ok((x, y));
+            };
+
+            let monitor = platform::monitors()
+                .into_iter()
+                .find(|monitor| &monitor.name == name)
+                .ok_or("TARGET MONITOR NOT FOUND")?;
+            let (left, top, right, bottom) = monitor.rect;
+            let (x, y) = (left + x, top + y);
+            if x < left || x >= right || y < top || y >= bottom {
+                return Err("TARGET IS OUTSIDE ITS MONITOR");
+            }
 
-							show_constant_interval_mode(ui, &mut h, &mut m, &mut s, &mut ms);
+            return Ok((x, y));
+        }
 
-							app.hours = h;
-							app.minutes = m;
-							app.seconds = s;
-							app.milliseconds = ms;
-						}
-						IntervalMode::Random => {
-							let mut min = app.interval_mode_random_min;
-							let mut max = app.interval_mode_random_max;
+        let title = self
+            .settings
+            .fixed_position_window
+            .as_deref()
+            .ok_or("NO TARGET WINDOW PICKED")?;
+        let (left, top, right, bottom) =
+            platform::find_window(title).ok_or("TARGET WINDOW NOT FOUND")?;
 
-							show_random_interval_mode(ui, &mut min, &mut max);
+        let (x, y) = (left + x, top + y);
+        if x < left || x >= right || y < top || y >= bottom {
+            return Err("TARGET IS OUTSIDE ITS WINDOW");
+        }
+        if !platform::point_on_screen(x, y) {
+            return Err("TARGET IS OFF-SCREEN");
+        }
 
-							app.interval_mode_random_min = min;
-							app.interval_mode_random_max = max;
-						}
-					}
+        Ok((x, y))
+    }
 
-					// ui.label(
-					//     RichText::new("Compact Mode").color(ui.style().visuals.strong_text_color()),
-					// );
-				},
-			);
-		});
+    /// Stores the current cursor position as the fixed position, relative to
+    /// the window under the cursor if that is enabled.
+    fn capture_fixed_position(&mut self) {
+        self.capturing_fixed_position = false;
+
+        let Ok(point) = self.mouse.get_position() else {
+            return;
+        };
+
+        if !self.settings.fixed_position_relative {
+            self.settings.fixed_position = (point.x, point.y);
+
+            // Stay relative to a monitor, but to the one the position was picked on
+            if self.settings.fixed_position_monitor.is_some() {
+                if let Some(monitor) = platform::monitors().into_iter().find(|monitor| {
+                    let (left, top, right, bottom) = monitor.rect;
+                    (left..right).contains(&point.x) && (top..bottom).contains(&point.y)
+                }) {
+                    self.settings.fixed_position = (point.x - monitor.rect.0, point.y - monitor.rect.1);
+                    self.settings.fixed_position_monitor = Some(monitor.name);
+                }
+            }
+        } else if let Some(window) = platform::window_at(point.x, point.y) {
+            self.settings.fixed_position = (point.x - window.rect.0, point.y - window.rect.1);
+            self.settings.fixed_position_window = Some(window.title);
+        }
     }
 
-    fn start_clicker(&self) {
-        let app_arc_clone = Arc::clone(&self.main_app);
-        thread::spawn(move || {
-            let mut holder = AppHolder {
-                main_app: app_arc_clone,
+    /// Turns Color Mode on or off. Turning it on reads the pixel under the
+    /// cursor right away, so the preview isn't stale from the last time.
+    fn toggle_color_mode(&mut self) {
+        self.settings.color_mode = !self.settings.color_mode;
+        if !self.settings.color_mode {
+            return;
+        }
+
+        if let Ok(pixel) = autopilot::screen::get_color(autopilot::mouse::location()) {
+            self.hovering_pixel_color = Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
+        }
+    }
+
+    /// Adds the window that was clicked to the Allowed Windows.
+    fn capture_allowed_window(&mut self) {
+        self.capturing_allowed_window = false;
+
+        let Ok(point) = self.mouse.get_position() else {
+            return;
+        };
+        if let Some(window) = platform::window_at(point.x, point.y) {
+            if !self.settings.allowed_windows.contains(&window.title) {
+                self.settings.allowed_windows.push(window.title);
+            }
+        }
+    }
+
+    /// Whether the window in front is one clicking is allowed over. Anything
+    /// is with no Allowed Windows set.
+    fn allowed_window_in_front(&self) -> bool {
+        self.settings.allowed_windows.is_empty()
+            || platform::foreground_window_title().is_some_and(|title| self.settings.allowed_windows.contains(&title))
+    }
+
+    /// Adds wherever was clicked to the end of the Click Points.
+    fn capture_click_point(&mut self) {
+        self.capturing_click_point = false;
+
+        if let Ok(point) = self.mouse.get_position() {
+            self.settings.click_points.push((point.x, point.y));
+        }
+    }
+
+    fn capture_watch_pixel(&mut self) {
+        self.capturing_watch_pixel = false;
+
+        if let Ok(point) = self.mouse.get_position() {
+            self.settings.watch_pixel = (point.x, point.y);
+        }
+    }
+
+    /// Stores the color of the pixel under the cursor as the Color Mode color.
+    fn capture_color(&mut self) {
+        self.capturing_color = false;
+
+        if let Ok(pixel) = autopilot::screen::get_color(autopilot::mouse::location()) {
+            self.settings.color_mode_color = Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
+        }
+    }
+
+    /// Runs one interval's worth of clicking and/or anti-idle movement. Returns
+    /// `false` if nothing was done because the click target isn't available.
+    fn perform_action(&mut self) -> Result<bool, String> {
+        if self.macro_active() {
+            return self.perform_macro_action();
+        }
+
+        // Where to put the cursor back after clicking off from it
+        let mut cursor_home = None;
+        if self.settings.action_type != ActionType::AntiIdle && !self.mouse_is_pressed {
+            match self.next_click_target() {
+                ClickTarget::Cursor => {
+                    let (dx, dy) = self.settings.cursor_offset;
+                    if (dx, dy) != (0, 0) {
+                        let point = self.mouse.get_position().map_err(|e| e.to_string())?;
+                        synthetic::mark();
+                        self.mouse.move_to(point.x + dx, point.y + dy).map_err(|e| e.to_string())?;
+                        cursor_home = Some((point.x, point.y));
+                    }
+                }
+                ClickTarget::Position(x, y) => {
+                    if self.settings.return_cursor {
+                        let point = self.mouse.get_position().map_err(|e| e.to_string())?;
+                        cursor_home = Some((point.x, point.y));
+                    }
+                    self.move_cursor(x, y)?;
+                    if self.settings.verify_cursor_position && !self.cursor_reached(x, y) {
+                        self.return_cursor(cursor_home);
+                        self.target_warning = Some("CURSOR DIDN'T REACH THE TARGET");
+                        return Ok(false);
+                    }
+                }
+                ClickTarget::Unavailable(reason) => {
+                    self.target_warning = Some(reason);
+                    return Ok(false);
+                }
+                ClickTarget::Skip => {
+                    self.target_warning = None;
+                    return Ok(false);
+                }
+            }
+            self.target_warning = None;
+        }
+
+        if self.settings.action_type == ActionType::Scroll {
+            let notches = match self.settings.scroll_direction {
+                ScrollDirection::Up => self.settings.scroll_amount as i32,
+                ScrollDirection::Down => -(self.settings.scroll_amount as i32),
             };
-            holder.click_loop();
-        });
+            synthetic::mark();
+            let result = self.click_backend().scroll(notches);
+            self.return_cursor(cursor_home);
+            synthetic::mark();
+            result?;
+        }
+
+        if self.settings.action_type.clicks() {
+            let (button, click_mode) = if self.mouse_is_pressed {
+                // A held Toggle press has to be released with the button that pressed it
+                (self.pressed_mouse_button, ClickMode::Toggle)
+            } else {
+                self.next_button_and_mode()
+            };
+
+            synthetic::mark();
+            let result = self.click_mouse(button, click_mode);
+            self.return_cursor(cursor_home);
+            synthetic::mark();
+            if click_mode == ClickMode::Toggle {
+                let pressed = self.mouse_is_pressed;
+                self.mouse_is_pressed = ToggleInput::next(pressed).held_after(pressed, &result);
+                if result.is_ok() {
+                    self.pressed_mouse_button = button;
+                }
+            }
+            self.physical_clicks += result?;
+        }
+
+        if self.settings.action_type.nudges() {
+            synthetic::mark();
+            self.nudge_mouse()?;
+        }
+
+        Ok(true)
     }
 
-    fn toggle_clicker(&mut self) {
-        let mut app = self.app_mut();
-        app.clicker_enabled = !app.clicker_enabled;
+    /// Whether the clicker plays back the macro instead of clicking on an interval.
+    fn macro_active(&self) -> bool {
+        self.settings.macro_enabled && !self.settings.macro_actions.is_empty()
     }
-}
 
-struct App {
-    mouse: Mouse,
+    /// Runs the next macro action, going back to the first after the last.
+    /// Only actions that click count as clicks.
+    fn perform_macro_action(&mut self) -> Result<bool, String> {
+        self.macro_index %= self.settings.macro_actions.len();
+        let action = self.settings.macro_actions[self.macro_index];
+        self.macro_index = (self.macro_index + 1) % self.settings.macro_actions.len();
 
-    interval_mode: IntervalMode,
-    hours: u32,
-    minutes: u32,
-    seconds: u32,
-    milliseconds: u32,
+        synthetic::mark();
+        self.mouse.move_to(action.x, action.y).map_err(|e| e.to_string())?;
+        if let Some(button) = action.button {
+            let result = self.click_backend().click(button);
+            synthetic::mark();
+            result?;
+            self.physical_clicks += 1;
+        }
 
-    interval_mode_random_min: f32,
-    interval_mode_random_max: f32,
+        Ok(action.button.is_some())
+    }
 
-    mouse_button: MouseButton,
-    click_mode: ClickMode,
+    /// Does the same work as `perform_action` without sending any input.
+    fn dry_run_action(&mut self) {
+        if self.macro_active() {
+            self.macro_index = (self.macro_index + 1) % self.settings.macro_actions.len();
+            return;
+        }
 
-    mouse_is_pressed: bool,
+        if self.settings.color_mode {
+            if let Ok(pixel) = autopilot::screen::get_color(autopilot::mouse::location()) {
+                self.hovering_pixel_color = Color32::from_rgb(pixel.0[0], pixel.0[1], pixel.0[2]);
+            }
+            self.color_matches();
+        }
 
-    clicker_id: u32,
+        if self.settings.action_type != ActionType::AntiIdle {
+            self.next_click_target();
+        }
+        if self.settings.action_type.clicks() {
+            self.next_button_and_mode();
+        }
+    }
 
-    color_mode: bool,
-    color_mode_color: Color32,
-    color_mode_distance_threshold: u8,
-    hovering_pixel_color: Color32,
+    /// Picks the button and click mode for the next click, advancing the step
+    /// sequence if it is enabled.
+    fn next_button_and_mode(&mut self) -> (MouseButton, ClickMode) {
+        if !self.settings.step_sequence_enabled || self.settings.click_steps.is_empty() {
+            return (self.pick_mouse_button(), self.settings.click_mode);
+        }
 
-    limit_mode: LimitMode,
-    limit_mode_clicks_amount: u32,
-    limit_mode_time: f32,
+        self.click_step_index %= self.settings.click_steps.len();
+        let step = self.settings.click_steps[self.click_step_index];
 
-    clicker_enabled: bool,
-    last_clicker_enabled: bool,
-    clicker_start_time: Instant,
-    total_clicks: u32,
+        self.click_step_clicks += 1;
+        if self.click_step_clicks >= step.repeat.max(1) {
+            self.click_step_clicks = 0;
+            self.click_step_index = (self.click_step_index + 1) % self.settings.click_steps.len();
+        }
 
-    always_on_top: bool,
-    focused: bool,
-    compact_mode: bool,
-}
+        (step.mouse_button, step.click_mode)
+    }
 
-impl App {
-    fn click_mouse(&self) {
-        let button = match self.mouse_button {
-            MouseButton::Left => mouse_rs::types::keys::Keys::LEFT,
-            MouseButton::Middle => mouse_rs::types::keys::Keys::MIDDLE,
-            MouseButton::Right => mouse_rs::types::keys::Keys::RIGHT,
-        };
+    fn click_backend(&self) -> &dyn ClickBackend {
+        match self.settings.click_backend {
+            ClickBackendKind::MouseRs => &self.mouse,
+            ClickBackendKind::SendInput => &SendInputBackend,
+        }
+    }
 
-        match self.click_mode {
-            ClickMode::Single => self.mouse.click(&button).expect("Unable to click button"),
+    /// Clicks in the given mode, returning how many times the button went down.
+    fn click_mouse(&self, mouse_button: MouseButton, click_mode: ClickMode) -> Result<u32, String> {
+        let backend = self.click_backend();
+
+        let presses = match click_mode {
+            ClickMode::Single => {
+                backend.click(mouse_button)?;
+                1
+            }
             ClickMode::Double => {
-                self.mouse.click(&button).expect("Unable to click button");
-                self.mouse.click(&button).expect("Unable to click button");
+                backend.click(mouse_button)?;
+                backend.click(mouse_button)?;
+                2
             }
-            ClickMode::Toggle => {
-                if self.mouse_is_pressed {
-                    self.mouse.press(&button).expect("Unable to press button");
-                } else {
-                    self.mouse
-                        .release(&button)
-                        .expect("Unable to release button");
+            ClickMode::SmartDouble => {
+                // Both clicks have to land on the same pixel within the OS double click time
+                let point = self.mouse.get_position().map_err(|e| e.to_string())?;
+                let step = (platform::double_click_time() / 8).min(SMART_DOUBLE_CLICK_STEP);
+
+                backend.press(mouse_button)?;
+                sleep(step);
+                backend.release(mouse_button)?;
+                sleep(step);
+                self.mouse.move_to(point.x, point.y).map_err(|e| e.to_string())?;
+                backend.press(mouse_button)?;
+                sleep(step);
+                backend.release(mouse_button)?;
+                2
+            }
+            ClickMode::Toggle => match ToggleInput::next(self.mouse_is_pressed) {
+                ToggleInput::Press => {
+                    backend.press(mouse_button)?;
+                    1
+                }
+                ToggleInput::Release => {
+                    backend.release(mouse_button)?;
+                    0
+                }
+            },
+        };
+
+        Ok(presses)
+    }
+
+    /// Moves the cursor to a click target, easing it along the way unless the
+    /// movement is instant.
+    fn move_cursor(&self, x: i32, y: i32) -> Result<(), String> {
+        let duration = Duration::from_millis(self.settings.movement_duration_ms as u64);
+        if self.settings.movement_easing == MovementEasing::Instant || duration.is_zero() {
+            return self.mouse.move_to(x, y).map_err(|e| e.to_string());
+        }
+
+        let from = self.mouse.get_position().map_err(|e| e.to_string())?;
+        let start = Instant::now();
+        loop {
+            let t = (start.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0);
+            let eased = self.settings.movement_easing.apply(t);
+            self.mouse
+                .move_to(
+                    from.x + ((x - from.x) as f32 * eased).round() as i32,
+                    from.y + ((y - from.y) as f32 * eased).round() as i32,
+                )
+                .map_err(|e| e.to_string())?;
+
+            if t >= 1.0 {
+                return Ok(());
+            }
+            sleep(MOVEMENT_STEP);
+        }
+    }
+
+    /// Moves the cursor back to where it was before clicking at the cursor
+    /// offset, so the offset doesn't add up from click to click. Best effort,
+    /// the click itself already went through or failed by now.
+    fn return_cursor(&self, home: Option<(i32, i32)>) {
+        if let Some((x, y)) = home {
+            let _ = self.mouse.move_to(x, y);
+        }
+    }
+
+    /// Puts the cursor in the middle of the primary monitor, to get it back
+    /// from wherever a clicker left it after stopping in a hurry.
+    fn recenter_cursor(&self) {
+        let (x, y) = match platform::monitors().first() {
+            Some(monitor) => {
+                let (left, top, right, bottom) = monitor.rect;
+                ((left + right) / 2, (top + bottom) / 2)
+            }
+            None => {
+                let size = autopilot::screen::size();
+                let scale = autopilot::screen::scale();
+                ((size.width * scale / 2.0) as i32, (size.height * scale / 2.0) as i32)
+            }
+        };
+
+        synthetic::mark();
+        if let Err(error) = self.mouse.move_to(x, y) {
+            self.toast(ToastLevel::Warning, format!("Couldn't move the cursor: {}", error));
+        }
+    }
+
+    /// Waits for the cursor to get within a pixel of where it was moved to, for
+    /// connections where moves land late. Gives up after the verify timeout.
+    fn cursor_reached(&self, x: i32, y: i32) -> bool {
+        let timeout = Duration::from_millis(self.settings.verify_cursor_timeout_ms as u64);
+        let start = Instant::now();
+        loop {
+            if let Ok(point) = self.mouse.get_position() {
+                if (point.x - x).abs() <= 1 && (point.y - y).abs() <= 1 {
+                    return true;
                 }
             }
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            sleep(CURSOR_VERIFY_STEP);
         }
     }
+
+    /// Wiggles the cursor a few pixels and back so the system registers activity.
+    fn nudge_mouse(&self) -> Result<(), String> {
+        let point = self.mouse.get_position().map_err(|e| e.to_string())?;
+        self.mouse
+            .move_to(point.x + self.settings.anti_idle_distance, point.y)
+            .map_err(|e| e.to_string())?;
+        self.mouse
+            .move_to(point.x, point.y)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Stops a running Click Burst before its next click. It still counts as
+    /// running until its thread has let go, so a new one can't start alongside.
+    fn cancel_key_burst(&mut self) {
+        self.key_burst_cancelled = self.key_burst_running;
+    }
+
     fn try_release_mouse(&mut self) {
         if !self.mouse_is_pressed {
             return;
         };
-        let button = match self.mouse_button {
-            MouseButton::Left => mouse_rs::types::keys::Keys::LEFT,
-            MouseButton::Middle => mouse_rs::types::keys::Keys::MIDDLE,
-            MouseButton::Right => mouse_rs::types::keys::Keys::RIGHT,
+        synthetic::mark();
+        let result = self.click_backend().release(self.pressed_mouse_button);
+        self.mouse_is_pressed = ToggleInput::Release.held_after(true, &result);
+        if let Err(error) = result {
+            self.input_error = Some(error);
+        }
+    }
+
+    /// Runs the gamepad's on release action for when the Hold button is let go.
+    fn perform_release_action(&mut self) {
+        let button = self.settings.mouse_button;
+        let result = match self.settings.gamepad_release_action {
+            ReleaseAction::Nothing => return,
+            ReleaseAction::Click => {
+                synthetic::mark();
+                self.click_backend().click(button)
+            }
+            ReleaseAction::Release => {
+                synthetic::mark();
+                self.click_backend().release(button)
+            }
         };
+        synthetic::mark();
 
-        self.mouse
-            .release(&button)
-            .expect("Unable to release button");
-        self.mouse_is_pressed = false;
+        if let Err(error) = result {
+            self.input_error = Some(error);
+        }
     }
-}
 
-impl eframe::App for AppHolder {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.menu_bar(ctx);
+    /// Clicks the selected mouse button once, for a tap of the gamepad Hold
+    /// button too short to start the clicker. Marked as our own input like
+    /// the clicker's clicks.
+    fn tap_click(&mut self) {
+        synthetic::mark();
+        let result = self.click_backend().click(self.settings.mouse_button);
+        synthetic::mark();
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            if self.app().compact_mode {
-                self.show_compact_menu(ui);
-            } else {
-                self.show_menu(ui);
+        if let Err(error) = result {
+            self.input_error = Some(error);
+        }
+    }
+
+    /// Stops the auto clicker and tells the user why.
+    fn stop_clicker(&mut self, level: ToastLevel, reason: &str) {
+        self.clicker_enabled = false;
+        self.toast(level, format!("{}: {}", self.name, reason));
+    }
+
+    /// Multiplies the interval by `factor`, staying within the ranges the
+    /// interval fields allow. The click loop reads the interval every click, so
+    /// this applies right away while clicking.
+    fn scale_interval(&mut self, factor: f64) {
+        let min = if self.settings.safe_mode { SAFE_MODE_MIN_INTERVAL } else { 0.0 };
+
+        match self.settings.interval_mode {
+            IntervalMode::Constant => {
+                let seconds = self.settings.constant_interval() * factor;
+                self.settings.set_constant_interval(seconds.clamp(min, MAX_INTERVAL));
             }
-        });
+            IntervalMode::Random => {
+                let max = (self.settings.interval_mode_random_max * factor as f32).clamp(min as f32, MAX_RANDOM_INTERVAL);
+                let min = (self.settings.interval_mode_random_min * factor as f32).clamp(min as f32, max);
 
-        let app = self.app();
-        let enabled_changed = app.clicker_enabled != app.last_clicker_enabled;
-        drop(app);
+                self.settings.interval_mode_random_min = min;
+                self.settings.interval_mode_random_max = max;
+            }
+        }
+    }
 
-        if enabled_changed {
-            let mut app = self.app_mut();
-            app.last_clicker_enabled = app.clicker_enabled;
+    /// Sets every click counter back to zero, like starting without Accumulate Clicks.
+    fn reset_counters(&mut self) {
+        self.session_clicks = 0;
+        self.total_clicks = 0;
+        self.session_ticks = 0;
+        self.session_attempts = 0;
+        self.physical_clicks = 0;
+        self.session_bursts = 0;
+        self.last_click_pos = None;
+        self.click_times.clear();
+    }
 
-            if app.clicker_enabled {
-                app.clicker_start_time = Instant::now();
-                drop(app);
-                self.start_clicker();
-            } else {
-                app.try_release_mouse();
+    /// The click shield's stats as plain text, for copying.
+    fn stats_text(&self) -> String {
+        let mut text = format!(
+            "Time: {:.2}s\nClicks: {}\nTotal: {}\nTicks: {}\nPhysical clicks: {}",
+            self.session_time().as_secs_f64(),
+            self.session_clicks,
+            self.total_clicks,
+            self.session_ticks,
+            self.physical_clicks,
+        );
+        if self.settings.attempt_limit_enabled {
+            text += &format!("\nAttempts: {}", self.session_attempts);
+        }
+        if self.settings.burst_enabled {
+            text += &format!("\nBursts: {}", self.session_bursts);
+        }
+        if let Some((x, y)) = self.last_click_pos {
+            text += &format!("\nLast position: {}, {}", x, y);
+        }
+        if let Some(stats) = IntervalStats::new(&self.click_intervals()) {
+            text += &format!("\nIntervals: {}", stats.summary());
+        }
+        text
+    }
+
+    /// How long the clicker has been clicking, zero until the first click.
+    fn session_time(&self) -> Duration {
+        if self.armed {
+            Duration::ZERO
+        } else {
+            self.clicker_start_time.elapsed()
+        }
+    }
+
+    /// Clicking while focused with nothing else holding the clicks back, which
+    /// can click this window as fast as the interval allows.
+    fn unsafe_focus_clicking(&self) -> bool {
+        self.settings.click_while_focused && !self.settings.color_mode
+    }
+
+    fn toggle_clicker(&mut self) {
+        // Stopping always works, starting needs a working mouse
+        if !self.clicker_enabled && !self.mouse_available {
+            return;
+        }
+        if !self.clicker_enabled && self.settings.require_arming && !self.master_armed {
+            self.toast(ToastLevel::Warning, "Arm the auto clicker before starting it");
+            return;
+        }
+        // Imported settings can turn it on without going through the checkbox
+        if !self.clicker_enabled && self.unsafe_focus_clicking() && !self.unsafe_focus_acknowledged {
+            self.confirming_unsafe_focus = true;
+            return;
+        }
+
+        self.clicker_enabled = !self.clicker_enabled;
+    }
+}
+
+impl eframe::App for AppHolder {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let dot_mode = self.app().settings.dot_mode;
+        self.track_window(ctx);
+        if !dot_mode {
+            self.menu_bar(ctx);
+            self.clicker_tabs(ctx);
+        }
+        if !self.global_hotkeys {
+            self.window_hotkeys(ctx);
+        }
+
+        self.commit_pending_edits(ctx);
+        if dot_mode {
+            self.dot_view(ctx);
+        } else {
+            if !self.app().settings.compact_mode {
+                self.status_strip(ctx);
             }
+            egui::CentralPanel::default().show(ctx, |ui| {
+                if self.app().settings.context_menu {
+                    self.main_context_menu(ui);
+                }
+                if self.app().settings.compact_mode {
+                    self.show_compact_menu(ui);
+                } else if self.page == Page::Keybinds {
+                    self.show_keybinds(ui);
+                } else {
+                    self.show_menu(ui);
+                }
+            });
+        }
+
+        let clickers = self.clickers.lock().unwrap().list.clone();
+        for app_arc in &clickers {
+            self.sync_clicker(app_arc, ctx);
         }
+        self.perform_limit_actions(ctx);
 
-        if self.app().clicker_enabled {
-            if self.app().compact_mode {
+        // With Shield On First Click, the window stays visible while waiting for the first click
+        let shield_held_back = self.app().settings.shield_on_first_click && self.app().armed;
+        if self.app().clicker_enabled && !shield_held_back && !dot_mode {
+            if self.app().settings.compact_mode {
                 self.compact_click_shield(ctx);
-            } else {
+            } else if !self.app().settings.edit_while_clicking {
                 self.click_shield(ctx);
             }
         }
 
+        self.unsafe_focus_confirmation(ctx);
+        self.whats_new_popup(ctx);
+        self.click_flash(ctx);
+        self.fixed_position_marker(ctx);
+        self.magnifier(ctx);
+        self.region_selector(ctx);
+        self.update_fullscreen_yield(ctx);
+        self.update_window_style();
+        self.update_window_corner(ctx);
+        self.toasts.lock().unwrap().show(ctx);
+
         ctx.request_repaint();
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        let clickers = self.clickers.lock().unwrap();
+        let saved = SavedTabs {
+            selected: clickers.selected,
+            tabs: clickers.list.iter().map(|app| app.lock().unwrap().settings.clone()).collect(),
+        };
+        drop(clickers);
+
+        if let Err(error) = saved.save_for_next_run() {
+            // The window is already gone, so there's nowhere to toast it
+            rfd::MessageDialog::new()
+                .set_level(rfd::MessageLevel::Error)
+                .set_title("Clickr")
+                .set_description(format!("Couldn't save the settings: {}", error))
+                .show();
+        }
+    }
+
+    fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
+        // Keeps the transparent marker viewport see-through
+        [0.0; 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Clicks the way `click_loop` does, checking the limit before every click.
+	fn clicks_until_limit(limit: u32) -> u32 {
+		let mut clicks = 0;
+		while !click_limit_reached(clicks, limit) {
+			clicks += 1;
+		}
+		clicks
+	}
+
+	#[test]
+	fn click_limit_stops_at_the_limit() {
+		assert!(!click_limit_reached(9, 10));
+		assert!(click_limit_reached(10, 10));
+		assert!(click_limit_reached(11, 10));
+		assert_eq!(clicks_until_limit(10), 10);
+	}
+
+	#[test]
+	fn click_limit_of_one_clicks_once() {
+		assert!(!click_limit_reached(0, 1));
+		assert!(click_limit_reached(1, 1));
+		assert_eq!(clicks_until_limit(1), 1);
+	}
+
+	#[test]
+	fn click_limit_of_zero_still_clicks_once() {
+		assert!(!click_limit_reached(0, 0));
+		assert_eq!(clicks_until_limit(0), 1);
+	}
+
+	#[test]
+	fn short_oversleep_is_not_a_suspend() {
+		let requested = Duration::from_secs(1);
+		assert_eq!(suspended_time(requested, requested), None);
+		assert_eq!(suspended_time(requested, requested + SUSPEND_THRESHOLD - Duration::from_millis(1)), None);
+		// Waking up early isn't a suspend either
+		assert_eq!(suspended_time(requested, Duration::from_millis(500)), None);
+	}
+
+	#[test]
+	fn oversleep_at_the_threshold_is_a_suspend() {
+		let requested = Duration::from_secs(1);
+		assert_eq!(suspended_time(requested, requested + SUSPEND_THRESHOLD), Some(SUSPEND_THRESHOLD));
+	}
+
+	#[test]
+	fn smooth_deadlines_dont_drift() {
+		let start = Instant::now();
+		let interval = Duration::from_millis(10);
+
+		let mut deadline = smooth_deadline(None, start, interval, Duration::ZERO);
+		assert_eq!(deadline, start + interval);
+		for clicks in 2..100 {
+			// Every click wakes up a little late, which mustn't push the next ones back
+			let now = deadline + Duration::from_millis(3);
+			deadline = smooth_deadline(Some(deadline), now, interval, Duration::ZERO);
+			assert_eq!(deadline, start + interval * clicks);
+		}
+	}
+
+	#[test]
+	fn smooth_deadlines_include_the_post_click_delay() {
+		let start = Instant::now();
+		let deadline = smooth_deadline(Some(start), start, Duration::from_millis(10), Duration::from_millis(5));
+		assert_eq!(deadline, start + Duration::from_millis(15));
+	}
+
+	#[test]
+	fn smooth_deadlines_catch_up_when_slightly_behind() {
+		let start = Instant::now();
+		let interval = Duration::from_millis(10);
+		let now = start + Duration::from_millis(15);
+
+		let deadline = smooth_deadline(Some(start), now, interval, Duration::ZERO);
+		assert_eq!(deadline, start + interval);
+		// Already due, so the next click doesn't wait at all
+		assert_eq!(deadline.saturating_duration_since(now), Duration::ZERO);
+	}
+
+	#[test]
+	fn smooth_deadlines_drop_clicks_when_far_behind() {
+		let start = Instant::now();
+		let interval = Duration::from_millis(10);
+		let now = start + Duration::from_millis(25);
+
+		assert_eq!(smooth_deadline(Some(start), now, interval, Duration::ZERO), now);
+	}
+
+	#[test]
+	fn oversleep_past_the_threshold_is_all_suspended() {
+		let requested = Duration::from_secs(1);
+		let overshoot = SUSPEND_THRESHOLD + Duration::from_secs(60);
+		assert_eq!(suspended_time(requested, requested + overshoot), Some(overshoot));
+	}
+
+	#[test]
+	fn no_intervals_have_no_stats() {
+		assert!(IntervalStats::new(&[]).is_none());
+	}
+
+	#[test]
+	fn a_single_interval_has_no_spread() {
+		let stats = IntervalStats::new(&[0.05]).unwrap();
+		assert_eq!(stats.min, 0.05);
+		assert_eq!(stats.max, 0.05);
+		assert_eq!(stats.mean, 0.05);
+		assert_eq!(stats.std_dev, 0.0);
+	}
+
+	#[test]
+	fn interval_stats_of_known_intervals() {
+		let stats = IntervalStats::new(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+		assert_eq!(stats.min, 2.0);
+		assert_eq!(stats.max, 9.0);
+		assert_eq!(stats.mean, 5.0);
+		assert_eq!(stats.std_dev, 2.0);
+	}
+
+	#[test]
+	fn hues_wrap_around_at_360_degrees() {
+		let red = Color32::from_rgb(255, 0, 0);
+		let pinkish_red = Color32::from_rgb(255, 0, 8);
+
+		let (hue, _, _) = rgb_to_hsv(pinkish_red);
+		assert!(hue > 355.0 && hue < 360.0);
+		assert_eq!(rgb_to_hsv(red).0, 0.0);
+
+		// Only a couple of degrees apart across 0, not over 350
+		assert!(hsv_colors_match(red, pinkish_red, (5, 0, 0)));
+		assert!(hsv_colors_match(pinkish_red, red, (5, 0, 0)));
+		assert!(!hsv_colors_match(red, pinkish_red, (1, 0, 0)));
+	}
+
+	#[test]
+	fn grays_and_black_have_a_defined_hue() {
+		for color in [Color32::BLACK, Color32::from_gray(128), Color32::WHITE] {
+			let (hue, saturation, _) = rgb_to_hsv(color);
+			assert_eq!(hue, 0.0);
+			assert_eq!(saturation, 0.0);
+		}
+	}
+
+	#[test]
+	fn grays_match_any_hue() {
+		let gray = Color32::from_gray(128);
+		let blueish_gray = Color32::from_rgb(124, 128, 132);
+		assert!(rgb_to_hsv(blueish_gray).0 > 180.0);
+		assert!(hsv_colors_match(gray, blueish_gray, (0, 10, 10)));
+		assert!(hsv_colors_match(Color32::BLACK, Color32::from_rgb(0, 0, 10), (0, 100, 5)));
+	}
+
+	/// Sends the next Toggle input, which goes through or fails with
+	/// `result`, and gives the input and whether the button is held after.
+	fn toggle_click(mouse_is_pressed: bool, result: Result<(), String>) -> (ToggleInput, bool) {
+		let input = ToggleInput::next(mouse_is_pressed);
+		(input, input.held_after(mouse_is_pressed, &result))
+	}
+
+	#[test]
+	fn toggle_presses_then_releases() {
+		assert_eq!(toggle_click(false, Ok(())), (ToggleInput::Press, true));
+		assert_eq!(toggle_click(true, Ok(())), (ToggleInput::Release, false));
+	}
+
+	#[test]
+	fn a_failed_toggle_press_leaves_the_button_up() {
+		let (_, held) = toggle_click(false, Err("blocked".to_owned()));
+		assert!(!held);
+		// So the next click tries to press again, not release
+		assert_eq!(toggle_click(held, Ok(())), (ToggleInput::Press, true));
+	}
+
+	#[test]
+	fn a_failed_toggle_release_is_retried_when_stopping() {
+		let (_, held) = toggle_click(false, Ok(()));
+		let (input, held) = toggle_click(held, Err("blocked".to_owned()));
+		assert_eq!(input, ToggleInput::Release);
+		assert!(held);
+
+		// Stopping releases it, and failing again still counts it as held for the next start
+		let held = ToggleInput::Release.held_after(held, &Err::<(), _>("blocked"));
+		assert!(held);
+		let held = ToggleInput::Release.held_after(held, &Ok::<_, ()>(()));
+		assert!(!held);
+	}
+
+	#[test]
+	fn random_and_constant_intervals_share_a_ceiling() {
+		let constant_max = MAX_CONSTANT_INTERVAL_MS as f64 / 1000.0;
+		assert!((constant_max - MAX_INTERVAL).abs() < 1e-9);
+		// An f32 only gets within a few milliseconds this far up
+		assert!((MAX_RANDOM_INTERVAL as f64 - MAX_INTERVAL).abs() < 0.005);
+	}
+
+	/// The first `count` random intervals and buttons of a session.
+	fn random_session(seed: Option<u32>, count: usize) -> Vec<(f64, MouseButton)> {
+		let mut rng = session_rng(seed);
+		let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+		(0..count)
+			.map(|_| {
+				let interval = random_interval(&mut rng, 0.05, 0.5);
+				(interval, random_mouse_button(&mut rng, &buttons, MouseButton::Left))
+			})
+			.collect()
+	}
+
+	#[test]
+	fn the_same_seed_repeats_the_session() {
+		assert_eq!(random_session(Some(1234), 50), random_session(Some(1234), 50));
+	}
+
+	#[test]
+	fn another_seed_changes_the_session() {
+		assert_ne!(random_session(Some(1234), 50), random_session(Some(4321), 50));
+	}
+
+	#[test]
+	fn interval_expressions_take_a_unit() {
+		assert_eq!(interval_from_expression("1000/15 ms"), Ok(1000.0 / 15.0 / 1000.0));
+		assert_eq!(interval_from_expression("1/8 s"), Ok(0.125));
+		assert_eq!(interval_from_expression(" 2 "), Ok(2.0));
+	}
+
+	#[test]
+	fn interval_expressions_out_of_range_are_errors() {
+		assert!(interval_from_expression("-1").is_err());
+		assert!(interval_from_expression("24*3600 s").is_err());
+		assert!(interval_from_expression("1/0 ms").is_err());
+	}
 }
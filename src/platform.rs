@@ -0,0 +1,465 @@
+//! Native helpers that only exist on some platforms. Every function has a
+//! fallback so callers never need their own `cfg` checks.
+
+use std::{path::PathBuf, time::Duration};
+
+/// Whether the current foreground window covers its whole monitor, like a
+/// fullscreen game or video player.
+#[cfg(windows)]
+pub fn foreground_window_is_fullscreen() -> bool {
+	use std::mem;
+	use winapi::um::winuser::{
+		GetDesktopWindow, GetForegroundWindow, GetMonitorInfoW, GetShellWindow, GetWindowRect,
+		MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+	};
+
+	unsafe {
+		let window = GetForegroundWindow();
+		if window.is_null() || window == GetDesktopWindow() || window == GetShellWindow() {
+			return false;
+		}
+
+		let mut rect = mem::zeroed();
+		if GetWindowRect(window, &mut rect) == 0 {
+			return false;
+		}
+
+		let mut info: MONITORINFO = mem::zeroed();
+		info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+		if GetMonitorInfoW(MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST), &mut info) == 0 {
+			return false;
+		}
+
+		let monitor = info.rcMonitor;
+		rect.left <= monitor.left
+			&& rect.top <= monitor.top
+			&& rect.right >= monitor.right
+			&& rect.bottom >= monitor.bottom
+	}
+}
+
+#[cfg(not(windows))]
+pub fn foreground_window_is_fullscreen() -> bool {
+	false
+}
+
+/// The title of the window in the foreground, if it has one.
+#[cfg(windows)]
+pub fn foreground_window_title() -> Option<String> {
+	use winapi::um::winuser::{GetForegroundWindow, GetWindowTextW};
+
+	unsafe {
+		let window = GetForegroundWindow();
+		if window.is_null() {
+			return None;
+		}
+
+		let mut title = [0u16; 256];
+		let length = GetWindowTextW(window, title.as_mut_ptr(), title.len() as i32);
+		(length > 0).then(|| String::from_utf16_lossy(&title[..length as usize]))
+	}
+}
+
+#[cfg(not(windows))]
+pub fn foreground_window_title() -> Option<String> {
+	None
+}
+
+pub struct WindowInfo {
+	pub title: String,
+	/// Left, top, right and bottom edges in screen pixels
+	pub rect: (i32, i32, i32, i32),
+}
+
+/// The top-level window at the given screen position.
+#[cfg(windows)]
+pub fn window_at(x: i32, y: i32) -> Option<WindowInfo> {
+	use std::mem;
+	use winapi::{
+		shared::windef::POINT,
+		um::winuser::{GetAncestor, GetWindowRect, GetWindowTextW, WindowFromPoint, GA_ROOT},
+	};
+
+	unsafe {
+		let window = WindowFromPoint(POINT { x, y });
+		if window.is_null() {
+			return None;
+		}
+		let window = GetAncestor(window, GA_ROOT);
+
+		let mut title = [0u16; 256];
+		let length = GetWindowTextW(window, title.as_mut_ptr(), title.len() as i32);
+		if length <= 0 {
+			return None;
+		}
+
+		let mut rect = mem::zeroed();
+		if GetWindowRect(window, &mut rect) == 0 {
+			return None;
+		}
+
+		Some(WindowInfo {
+			title: String::from_utf16_lossy(&title[..length as usize]),
+			rect: (rect.left, rect.top, rect.right, rect.bottom),
+		})
+	}
+}
+
+#[cfg(not(windows))]
+pub fn window_at(_x: i32, _y: i32) -> Option<WindowInfo> {
+	None
+}
+
+/// The screen rect of the visible, non-minimized window with the given title.
+#[cfg(windows)]
+pub fn find_window(title: &str) -> Option<(i32, i32, i32, i32)> {
+	use std::{mem, ptr};
+	use winapi::um::winuser::{FindWindowW, GetWindowRect, IsIconic, IsWindowVisible};
+
+	let title: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+
+	unsafe {
+		let window = FindWindowW(ptr::null(), title.as_ptr());
+		if window.is_null() || IsWindowVisible(window) == 0 || IsIconic(window) != 0 {
+			return None;
+		}
+
+		let mut rect = mem::zeroed();
+		if GetWindowRect(window, &mut rect) == 0 {
+			return None;
+		}
+
+		Some((rect.left, rect.top, rect.right, rect.bottom))
+	}
+}
+
+#[cfg(not(windows))]
+pub fn find_window(_title: &str) -> Option<(i32, i32, i32, i32)> {
+	None
+}
+
+/// Whether the given screen position is on any monitor.
+#[cfg(windows)]
+pub fn point_on_screen(x: i32, y: i32) -> bool {
+	use winapi::{
+		shared::windef::POINT,
+		um::winuser::{MonitorFromPoint, MONITOR_DEFAULTTONULL},
+	};
+
+	unsafe { !MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONULL).is_null() }
+}
+
+#[cfg(not(windows))]
+pub fn point_on_screen(_x: i32, _y: i32) -> bool {
+	true
+}
+
+/// How quickly two clicks have to follow each other to count as a double click.
+#[cfg(windows)]
+pub fn double_click_time() -> Duration {
+	use winapi::um::winuser::GetDoubleClickTime;
+
+	Duration::from_millis(unsafe { GetDoubleClickTime() } as u64)
+}
+
+#[cfg(not(windows))]
+pub fn double_click_time() -> Duration {
+	Duration::from_millis(500)
+}
+
+/// The local time of day in minutes since midnight.
+#[cfg(windows)]
+pub fn local_minute_of_day() -> u32 {
+	use std::mem;
+	use winapi::um::sysinfoapi::GetLocalTime;
+
+	unsafe {
+		let mut time = mem::zeroed();
+		GetLocalTime(&mut time);
+		time.wHour as u32 * 60 + time.wMinute as u32
+	}
+}
+
+/// Without a time zone to go on this is the time of day in UTC.
+#[cfg(not(windows))]
+pub fn local_minute_of_day() -> u32 {
+	use std::time::{SystemTime, UNIX_EPOCH};
+
+	let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+	(seconds / 60 % (24 * 60)) as u32
+}
+
+pub struct BatteryInfo {
+	pub percent: u8,
+	/// Plugged in, whether or not it's actually charging
+	pub charging: bool,
+}
+
+/// The battery's charge, or nothing on computers without one.
+#[cfg(windows)]
+pub fn battery() -> Option<BatteryInfo> {
+	use std::mem;
+	use winapi::um::winbase::GetSystemPowerStatus;
+
+	const NO_SYSTEM_BATTERY: u8 = 128;
+	const UNKNOWN: u8 = 255;
+
+	unsafe {
+		let mut status = mem::zeroed();
+		if GetSystemPowerStatus(&mut status) == 0
+			|| status.BatteryFlag & NO_SYSTEM_BATTERY != 0
+			|| status.BatteryFlag == UNKNOWN
+			|| status.BatteryLifePercent == UNKNOWN
+		{
+			return None;
+		}
+
+		Some(BatteryInfo {
+			percent: status.BatteryLifePercent,
+			charging: status.ACLineStatus == 1,
+		})
+	}
+}
+
+#[cfg(not(windows))]
+pub fn battery() -> Option<BatteryInfo> {
+	None
+}
+
+pub struct MonitorInfo {
+	/// The device name, which stays the same when the monitor layout changes
+	pub name: String,
+	/// Left, top, right and bottom edges in screen pixels
+	pub rect: (i32, i32, i32, i32),
+	/// The part of `rect` not covered by the taskbar, in the same order
+	pub work_area: (i32, i32, i32, i32),
+}
+
+/// All connected monitors, the primary one first.
+#[cfg(windows)]
+pub fn monitors() -> Vec<MonitorInfo> {
+	use std::{mem, ptr};
+	use winapi::{
+		shared::{
+			minwindef::{BOOL, LPARAM, TRUE},
+			windef::{HDC, HMONITOR, LPRECT},
+		},
+		um::winuser::{EnumDisplayMonitors, GetMonitorInfoW, MONITORINFOEXW, MONITORINFOF_PRIMARY},
+	};
+
+	unsafe extern "system" fn callback(monitor: HMONITOR, _: HDC, _: LPRECT, data: LPARAM) -> BOOL {
+		let monitors = &mut *(data as *mut Vec<(bool, MonitorInfo)>);
+
+		let mut info: MONITORINFOEXW = mem::zeroed();
+		info.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+		if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut _) != 0 {
+			let length = info.szDevice.iter().position(|c| *c == 0).unwrap_or(info.szDevice.len());
+			let rect = info.rcMonitor;
+			let work_area = info.rcWork;
+			monitors.push((
+				info.dwFlags & MONITORINFOF_PRIMARY != 0,
+				MonitorInfo {
+					name: String::from_utf16_lossy(&info.szDevice[..length]),
+					rect: (rect.left, rect.top, rect.right, rect.bottom),
+					work_area: (work_area.left, work_area.top, work_area.right, work_area.bottom),
+				},
+			));
+		}
+
+		TRUE
+	}
+
+	let mut monitors: Vec<(bool, MonitorInfo)> = Vec::new();
+	unsafe {
+		EnumDisplayMonitors(
+			ptr::null_mut(),
+			ptr::null(),
+			Some(callback),
+			&mut monitors as *mut Vec<(bool, MonitorInfo)> as LPARAM,
+		);
+	}
+
+	monitors.sort_by_key(|(primary, _)| !primary);
+	monitors.into_iter().map(|(_, monitor)| monitor).collect()
+}
+
+#[cfg(not(windows))]
+pub fn monitors() -> Vec<MonitorInfo> {
+	Vec::new()
+}
+
+/// Sets the opacity of the window with the given title and whether it shows
+/// up in the taskbar. Returns `false` if the window wasn't found.
+#[cfg(windows)]
+pub fn style_window(title: &str, opacity: f32, in_taskbar: bool) -> bool {
+	use std::ptr;
+	use winapi::um::winuser::{
+		FindWindowW, GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, ShowWindow, GWL_EXSTYLE,
+		LWA_ALPHA, SW_HIDE, SW_SHOW, WS_EX_APPWINDOW, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
+	};
+
+	let title: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+
+	unsafe {
+		let window = FindWindowW(ptr::null(), title.as_ptr());
+		if window.is_null() {
+			return false;
+		}
+
+		let old_style = GetWindowLongPtrW(window, GWL_EXSTYLE) as u32;
+		let mut style = old_style | WS_EX_LAYERED;
+		if in_taskbar {
+			style = (style & !WS_EX_TOOLWINDOW) | WS_EX_APPWINDOW;
+		} else {
+			style = (style & !WS_EX_APPWINDOW) | WS_EX_TOOLWINDOW;
+		}
+
+		if style != old_style {
+			SetWindowLongPtrW(window, GWL_EXSTYLE, style as isize);
+			// The taskbar only notices the change when the window is shown again
+			if (style ^ old_style) & WS_EX_TOOLWINDOW != 0 {
+				ShowWindow(window, SW_HIDE);
+				ShowWindow(window, SW_SHOW);
+			}
+		}
+
+		let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+		SetLayeredWindowAttributes(window, 0, alpha, LWA_ALPHA) != 0
+	}
+}
+
+#[cfg(not(windows))]
+pub fn style_window(_title: &str, _opacity: f32, _in_taskbar: bool) -> bool {
+	false
+}
+
+/// Flashes the taskbar button of the window with the given title until the
+/// window is brought to the front.
+#[cfg(windows)]
+pub fn flash_taskbar(title: &str) {
+	use std::{mem, ptr};
+	use winapi::um::winuser::{FindWindowW, FlashWindowEx, FLASHWINFO, FLASHW_TIMERNOFG, FLASHW_TRAY};
+
+	let title: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+
+	unsafe {
+		let window = FindWindowW(ptr::null(), title.as_ptr());
+		if window.is_null() {
+			return;
+		}
+
+		let mut info = FLASHWINFO {
+			cbSize: mem::size_of::<FLASHWINFO>() as u32,
+			hwnd: window,
+			dwFlags: FLASHW_TRAY | FLASHW_TIMERNOFG,
+			uCount: 0,
+			dwTimeout: 0,
+		};
+		FlashWindowEx(&mut info);
+	}
+}
+
+#[cfg(not(windows))]
+pub fn flash_taskbar(_title: &str) {}
+
+/// Reads how loud the sound playing on the default output device is.
+#[cfg(windows)]
+pub struct AudioMeter(*mut winapi::um::endpointvolume::IAudioMeterInformation);
+
+#[cfg(windows)]
+impl AudioMeter {
+	/// Opens the meter of the default output device, if there is one.
+	pub fn new() -> Option<AudioMeter> {
+		use std::ptr;
+		use winapi::{
+			um::{
+				combaseapi::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL},
+				endpointvolume::IAudioMeterInformation,
+				mmdeviceapi::{eConsole, eRender, CLSID_MMDeviceEnumerator, IMMDevice, IMMDeviceEnumerator},
+				objbase::COINIT_MULTITHREADED,
+			},
+			Interface,
+		};
+
+		unsafe {
+			// Fails harmlessly if the thread already initialized COM
+			CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED);
+
+			let mut enumerator: *mut IMMDeviceEnumerator = ptr::null_mut();
+			let result = CoCreateInstance(
+				&CLSID_MMDeviceEnumerator,
+				ptr::null_mut(),
+				CLSCTX_ALL,
+				&IMMDeviceEnumerator::uuidof(),
+				&mut enumerator as *mut _ as *mut _,
+			);
+			if result < 0 {
+				return None;
+			}
+
+			let mut device: *mut IMMDevice = ptr::null_mut();
+			let result = (*enumerator).GetDefaultAudioEndpoint(eRender, eConsole, &mut device);
+			(*enumerator).Release();
+			if result < 0 {
+				return None;
+			}
+
+			let mut meter: *mut IAudioMeterInformation = ptr::null_mut();
+			let result = (*device).Activate(
+				&IAudioMeterInformation::uuidof(),
+				CLSCTX_ALL,
+				ptr::null_mut(),
+				&mut meter as *mut _ as *mut _,
+			);
+			(*device).Release();
+			if result < 0 {
+				return None;
+			}
+
+			Some(AudioMeter(meter))
+		}
+	}
+
+	/// The loudest sample of the last few milliseconds, from 0 to 1. Nothing
+	/// once the device is gone.
+	pub fn peak(&self) -> Option<f32> {
+		let mut peak = 0.0;
+		unsafe { ((*self.0).GetPeakValue(&mut peak) >= 0).then_some(peak) }
+	}
+}
+
+#[cfg(windows)]
+impl Drop for AudioMeter {
+	fn drop(&mut self) {
+		unsafe {
+			(*self.0).Release();
+		}
+	}
+}
+
+#[cfg(not(windows))]
+pub struct AudioMeter;
+
+#[cfg(not(windows))]
+impl AudioMeter {
+	pub fn new() -> Option<AudioMeter> {
+		None
+	}
+
+	pub fn peak(&self) -> Option<f32> {
+		None
+	}
+}
+
+/// Where Clickr keeps its files, like `%APPDATA%\Clickr` on Windows. It isn't
+/// created here.
+pub fn config_dir() -> Option<PathBuf> {
+	#[cfg(windows)]
+	let base = std::env::var_os("APPDATA").map(PathBuf::from);
+	#[cfg(not(windows))]
+	let base = std::env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+	base.map(|base| base.join("Clickr"))
+}
@@ -0,0 +1,103 @@
+//! Profiles are named copies of the settings, each kept as its own file in
+//! the `profiles` folder of the config folder, to switch between setups.
+
+use std::{fs, path::PathBuf};
+
+use crate::{platform, settings::Settings};
+
+/// Characters Windows doesn't allow in file names.
+const INVALID_CHARACTERS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+/// The names of every saved profile, sorted.
+pub fn list() -> Vec<String> {
+	let Some(dir) = dir() else {
+		return Vec::new();
+	};
+	let Ok(entries) = fs::read_dir(dir) else {
+		return Vec::new();
+	};
+
+	let mut names: Vec<String> = entries
+		.filter_map(|entry| {
+			let path = entry.ok()?.path();
+			if path.extension()? != "json" {
+				return None;
+			}
+			Some(path.file_stem()?.to_string_lossy().into_owned())
+		})
+		.collect();
+	names.sort_by_key(|name| name.to_lowercase());
+
+	names
+}
+
+pub fn load(name: &str) -> Result<(Settings, Option<String>), String> {
+	Settings::load(&path(name)?)
+}
+
+/// Saves the settings as the profile `name`, replacing it if it exists.
+pub fn save(name: &str, settings: &Settings) -> Result<(), String> {
+	let path = path(name)?;
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+	}
+	settings.save(&path)
+}
+
+/// Renames the profile `name`. Changing just the case of the name works too.
+pub fn rename(name: &str, new_name: &str) -> Result<(), String> {
+	let new_path = path(new_name)?;
+	// On Windows the new name finds the profile itself when only the case changes
+	if !same_name(name, new_name) && new_path.exists() {
+		return Err(format!("There's already a profile called {}", new_name.trim()));
+	}
+	fs::rename(path(name)?, new_path).map_err(|e| e.to_string())
+}
+
+pub fn delete(name: &str) -> Result<(), String> {
+	fs::remove_file(path(name)?).map_err(|e| e.to_string())
+}
+
+/// Whether two profile names are kept in the same file. Windows file names
+/// don't tell upper and lower case apart.
+pub fn same_name(a: &str, b: &str) -> bool {
+	let (a, b) = (a.trim(), b.trim());
+	if cfg!(windows) {
+		a.to_lowercase() == b.to_lowercase()
+	} else {
+		a == b
+	}
+}
+
+fn dir() -> Option<PathBuf> {
+	platform::config_dir().map(|dir| dir.join("profiles"))
+}
+
+/// The file the profile `name` is kept in, if it's a name a file can have.
+fn path(name: &str) -> Result<PathBuf, String> {
+	let name = name.trim();
+	if name.is_empty() {
+		return Err("The profile needs a name".to_owned());
+	}
+	if name.contains(INVALID_CHARACTERS) {
+		return Err("Profile names can't contain \\ / : * ? \" < > |".to_owned());
+	}
+
+	Ok(dir().ok_or("No config folder found")?.join(format!("{}.json", name)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn names_are_compared_without_surrounding_spaces() {
+		assert!(same_name("Fishing", " Fishing "));
+		assert!(!same_name("Fishing", "Mining"));
+	}
+
+	#[test]
+	fn names_differing_in_case_share_a_file_only_on_windows() {
+		assert_eq!(same_name("Fishing", "fishing"), cfg!(windows));
+	}
+}
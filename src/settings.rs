@@ -0,0 +1,762 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+	backend::ClickBackendKind,
+	gamepad::{GamepadButton, GamepadMode, ReleaseAction},
+	keybinds::{self, Keybinds},
+	macros::{self, MacroAction},
+	platform, ActionType, ClickMode, ColorMetric, FocusBehavior, ClickStep, IntervalMode, LimitAction, LimitMode, MouseButton, MovementEasing, RegionMode, ScrollDirection, WindowCorner, MAX_RANDOM_INTERVAL, MAX_SCREEN_COORDINATE, SAFE_MODE_MIN_INTERVAL,
+};
+
+/// The version of the settings format this build writes. Bump it and add a
+/// step to `migrate` whenever a field is renamed or changes meaning.
+pub const SETTINGS_VERSION: u32 = 2;
+
+/// The file in the config folder the clicker tabs are kept in between runs.
+const SAVED_SETTINGS_FILE: &str = "settings.json";
+/// Where a saved settings file that couldn't be loaded is moved before it's
+/// replaced, so a broken file or one from a newer version isn't lost.
+const SAVED_SETTINGS_BACKUP: &str = "settings.json.bak";
+
+/// Everything the user can configure, kept apart from the runtime state in
+/// `App` so it can be written to and read from disk.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+	/// The settings format the file was written in, see `SETTINGS_VERSION`
+	pub version: u32,
+
+	pub interval_mode: IntervalMode,
+	pub hours: u32,
+	pub minutes: u32,
+	pub seconds: u32,
+	pub milliseconds: u32,
+
+	pub interval_mode_random_min: f32,
+	pub interval_mode_random_max: f32,
+	pub type_values: bool,
+	pub interval_slider: bool,
+
+	pub post_click_delay_ms: u32,
+	pub max_cps_enabled: bool,
+	pub max_cps: f32,
+	pub smooth_timing: bool,
+	pub interval_from_click_start: bool,
+	pub prime_click: bool,
+	pub snap_to_cps: bool,
+	pub safe_mode: bool,
+
+	pub action_type: ActionType,
+	pub anti_idle_distance: i32,
+	pub scroll_direction: ScrollDirection,
+	pub scroll_amount: u32,
+
+	pub mouse_button: MouseButton,
+	pub click_mode: ClickMode,
+	pub click_backend: ClickBackendKind,
+
+	pub random_mouse_button: bool,
+	pub random_mouse_buttons: Vec<MouseButton>,
+
+	pub burst_enabled: bool,
+	pub burst_size: u32,
+	/// Seconds to wait after each burst, on top of the interval
+	pub burst_pause: f32,
+	/// Whether the clicks within a burst wait `burst_spacing_ms` instead of the interval
+	pub burst_spacing_enabled: bool,
+	pub burst_spacing_ms: u32,
+	pub random_button_per_burst: bool,
+
+	pub step_sequence_enabled: bool,
+	pub click_steps: Vec<ClickStep>,
+
+	pub click_points: Vec<(i32, i32)>,
+	pub movement_easing: MovementEasing,
+	pub movement_duration_ms: u32,
+	pub cursor_offset: (i32, i32),
+	/// Whether to move the cursor back after clicking somewhere other than at it
+	pub return_cursor: bool,
+	pub verify_cursor_position: bool,
+	pub verify_cursor_timeout_ms: u32,
+
+	pub macro_enabled: bool,
+	pub macro_actions: Vec<MacroAction>,
+
+	pub template_enabled: bool,
+	/// The image to find on screen and click the center of
+	pub template_path: Option<PathBuf>,
+	/// How alike a spot has to look to the template to count, 1 is identical
+	pub template_threshold: f32,
+	pub template_warn_missing: bool,
+
+	pub fixed_position_enabled: bool,
+	pub fixed_position: (i32, i32),
+	pub fixed_position_relative: bool,
+	pub fixed_position_window: Option<String>,
+	/// The device name of the monitor the fixed position is relative to
+	pub fixed_position_monitor: Option<String>,
+	pub show_fixed_position_marker: bool,
+	pub fixed_position_marker_color: Color32,
+
+	pub region_mode: RegionMode,
+	/// Left, top, width and height in screen pixels
+	pub click_region: (i32, i32, i32, i32),
+
+	pub color_mode: bool,
+	pub color_mode_color: Color32,
+	pub color_mode_metric: ColorMetric,
+	pub color_mode_distance_threshold: u8,
+	/// Hue in degrees, saturation and value in percent
+	pub color_mode_hsv_tolerance: (u16, u8, u8),
+	pub color_mode_pause_after: u32,
+
+	pub audio_trigger: bool,
+	/// How loud the output has to be to click, in percent
+	pub audio_threshold: u8,
+
+	pub limit_mode: LimitMode,
+	pub limit_mode_clicks_amount: u32,
+	pub limit_mode_time: f32,
+	pub limit_action: LimitAction,
+	pub attempt_limit_enabled: bool,
+	pub attempt_limit: u32,
+	pub flash_on_limit: bool,
+	pub watch_pixel: (i32, i32),
+	pub watch_pixel_threshold: u8,
+
+	pub safety_cap_enabled: bool,
+	pub safety_cap_minutes: u32,
+	pub safety_cap_clicks: u32,
+
+	pub idle_timeout_enabled: bool,
+	pub idle_timeout_minutes: u32,
+	pub active_hours_enabled: bool,
+	/// Hour and minute of the local time
+	pub active_hours_start: (u32, u32),
+	pub active_hours_end: (u32, u32),
+	pub battery_pause_enabled: bool,
+	pub battery_pause_below: u8,
+	pub battery_resume: bool,
+	/// Don't count our own clicks as user input
+	pub ignore_synthetic_input: bool,
+	pub click_while_focused: bool,
+	pub focus_behavior: FocusBehavior,
+	/// Titles of the windows clicking is allowed over, or anywhere if empty
+	pub allowed_windows: Vec<String>,
+	pub require_arming: bool,
+	pub save_on_crash: bool,
+	pub control_server_enabled: bool,
+	pub control_server_port: u16,
+
+	pub accumulate_clicks: bool,
+	pub show_interval_graph: bool,
+	pub click_flash: bool,
+	pub show_interval_accuracy: bool,
+	pub show_click_position: bool,
+	pub edit_while_clicking: bool,
+	pub shield_on_first_click: bool,
+	pub random_seed_enabled: bool,
+	pub random_seed: u32,
+	pub context_menu: bool,
+
+	pub always_on_top: bool,
+	pub yield_to_fullscreen: bool,
+	pub compact_mode: bool,
+	pub dot_mode: bool,
+	pub window_opacity: f32,
+	pub show_in_taskbar: bool,
+	pub shield_opacity: u8,
+	pub window_corner: WindowCorner,
+	/// Distance from the edges of the work area in pixels
+	pub window_corner_margin: u32,
+
+	pub keybinds: Keybinds,
+	pub stop_all_recenter: bool,
+	pub key_burst_clicks: u32,
+	pub key_burst_interval_ms: u32,
+
+	pub gamepad_enabled: bool,
+	pub gamepad_button: GamepadButton,
+	pub gamepad_mode: GamepadMode,
+	pub gamepad_release_action: ReleaseAction,
+	pub hold_activation_delay_ms: u32,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Settings {
+			version: SETTINGS_VERSION,
+
+			interval_mode: IntervalMode::Constant,
+			hours: 0,
+			minutes: 0,
+			seconds: 0,
+			milliseconds: 100,
+
+			interval_mode_random_min: 1.0,
+			interval_mode_random_max: 2.0,
+			type_values: false,
+			interval_slider: false,
+
+			post_click_delay_ms: 0,
+			max_cps_enabled: false,
+			max_cps: 20.0,
+			smooth_timing: false,
+			interval_from_click_start: false,
+			prime_click: false,
+			snap_to_cps: false,
+			safe_mode: true,
+
+			action_type: ActionType::Click,
+			anti_idle_distance: 1,
+			scroll_direction: ScrollDirection::Down,
+			scroll_amount: 1,
+
+			mouse_button: MouseButton::Left,
+			click_mode: ClickMode::Single,
+			click_backend: ClickBackendKind::MouseRs,
+
+			random_mouse_button: false,
+			random_mouse_buttons: vec![MouseButton::Left, MouseButton::Right],
+
+			burst_enabled: false,
+			burst_size: 5,
+			burst_pause: 1.0,
+			burst_spacing_enabled: false,
+			burst_spacing_ms: 20,
+			random_button_per_burst: false,
+
+			step_sequence_enabled: false,
+			click_steps: vec![
+				ClickStep {
+					mouse_button: MouseButton::Left,
+					click_mode: ClickMode::Single,
+					repeat: 10,
+				},
+				ClickStep {
+					mouse_button: MouseButton::Right,
+					click_mode: ClickMode::Single,
+					repeat: 1,
+				},
+			],
+
+			click_points: Vec::new(),
+			movement_easing: MovementEasing::Instant,
+			movement_duration_ms: 100,
+			cursor_offset: (0, 0),
+			return_cursor: false,
+			verify_cursor_position: false,
+			verify_cursor_timeout_ms: 100,
+
+			macro_enabled: false,
+			macro_actions: Vec::new(),
+
+			template_enabled: false,
+			template_path: None,
+			template_threshold: 0.9,
+			template_warn_missing: true,
+
+			fixed_position_enabled: false,
+			fixed_position: (0, 0),
+			fixed_position_relative: false,
+			fixed_position_window: None,
+			fixed_position_monitor: None,
+			show_fixed_position_marker: true,
+			fixed_position_marker_color: Color32::from_rgb(255, 0, 0),
+
+			region_mode: RegionMode::None,
+			click_region: (0, 0, 100, 100),
+
+			color_mode: false,
+			color_mode_color: Color32::BLACK,
+			color_mode_metric: ColorMetric::Rgb,
+			color_mode_distance_threshold: 0,
+			color_mode_hsv_tolerance: (10, 20, 20),
+			color_mode_pause_after: 0,
+
+			audio_trigger: false,
+			audio_threshold: 20,
+
+			limit_mode: LimitMode::None,
+			limit_mode_clicks_amount: 10,
+			limit_mode_time: 1.0,
+			limit_action: LimitAction::Stop,
+			attempt_limit_enabled: false,
+			attempt_limit: 1000,
+			flash_on_limit: false,
+			watch_pixel: (0, 0),
+			watch_pixel_threshold: 10,
+
+			safety_cap_enabled: true,
+			safety_cap_minutes: 60,
+			safety_cap_clicks: 1_000_000,
+
+			idle_timeout_enabled: false,
+			idle_timeout_minutes: 10,
+			active_hours_enabled: false,
+			active_hours_start: (9, 0),
+			active_hours_end: (17, 0),
+			battery_pause_enabled: false,
+			battery_pause_below: 20,
+			battery_resume: true,
+			ignore_synthetic_input: true,
+			click_while_focused: false,
+			focus_behavior: FocusBehavior::Pause,
+			allowed_windows: Vec::new(),
+			require_arming: false,
+			save_on_crash: false,
+			control_server_enabled: false,
+			control_server_port: 47_600,
+
+			accumulate_clicks: false,
+			show_interval_graph: false,
+			click_flash: false,
+			show_interval_accuracy: false,
+			show_click_position: false,
+			edit_while_clicking: false,
+			shield_on_first_click: false,
+			random_seed_enabled: false,
+			random_seed: 0,
+			context_menu: true,
+
+			always_on_top: true,
+			yield_to_fullscreen: false,
+			compact_mode: false,
+			dot_mode: false,
+			window_opacity: 1.0,
+			show_in_taskbar: true,
+			shield_opacity: 200,
+			window_corner: WindowCorner::None,
+			window_corner_margin: 10,
+
+			keybinds: Keybinds::default(),
+			stop_all_recenter: false,
+			key_burst_clicks: 10,
+			key_burst_interval_ms: 10,
+
+			gamepad_enabled: false,
+			gamepad_button: GamepadButton::South,
+			gamepad_mode: GamepadMode::Toggle,
+			gamepad_release_action: ReleaseAction::Nothing,
+			hold_activation_delay_ms: 0,
+		}
+	}
+}
+
+impl Settings {
+	/// Reads and validates settings from a JSON file, upgrading files written
+	/// by older versions first. Nothing is returned unless the whole file is
+	/// valid, so a bad file can never be half applied.
+	///
+	/// A file from a newer version can't be trusted to mean the same thing, so
+	/// the defaults are returned instead along with a warning for the user.
+	/// Callers that replace settings the user already has should refuse it.
+	pub fn load(path: &Path) -> Result<(Settings, Option<String>), String> {
+		let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+		Settings::from_json(&text)
+	}
+
+	/// Parses settings JSON the same way `load` does.
+	fn from_json(text: &str) -> Result<(Settings, Option<String>), String> {
+		Settings::from_value(serde_json::from_str(text).map_err(|e| e.to_string())?)
+	}
+
+	fn from_value(mut value: Value) -> Result<(Settings, Option<String>), String> {
+		// Files from before the format was versioned count as version 1
+		let version = match value.get("version") {
+			None => 1,
+			Some(version) => version
+				.as_u64()
+				.and_then(|version| u32::try_from(version).ok())
+				.ok_or("Settings version is not a number")?,
+		};
+		if version > SETTINGS_VERSION {
+			return Ok((
+				Settings::default(),
+				Some(format!(
+					"These settings are from a newer version of Clickr (format {})",
+					version
+				)),
+			));
+		}
+
+		migrate(&mut value, version)?;
+		let settings: Settings = serde_json::from_value(value).map_err(|e| e.to_string())?;
+		settings.validate()?;
+
+		Ok((settings, None))
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), String> {
+		let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+		fs::write(path, text).map_err(|e| e.to_string())
+	}
+
+	/// The constant interval in seconds.
+	pub fn constant_interval(&self) -> f64 {
+		self.hours as f64 * 3600.0 + self.minutes as f64 * 60.0 + self.seconds as f64 + self.milliseconds as f64 / 1000.0
+	}
+
+	/// Splits `seconds` into the constant interval fields, rounded to the
+	/// millisecond. It has to be within the range the fields can hold.
+	pub fn set_constant_interval(&mut self, seconds: f64) {
+		let millis = (seconds * 1000.0).round() as u32;
+
+		self.hours = millis / 3_600_000;
+		self.minutes = millis / 60_000 % 60;
+		self.seconds = millis / 1000 % 60;
+		self.milliseconds = millis % 1000;
+	}
+
+	/// The shortest time the clicker waits between clicks, from Safe Mode and
+	/// Max Clicks Per Second, whichever is longer.
+	pub fn min_interval(&self) -> f64 {
+		let min = if self.safe_mode { SAFE_MODE_MIN_INTERVAL } else { 0.0 };
+		if self.max_cps_enabled {
+			min.max(1.0 / self.max_cps as f64)
+		} else {
+			min
+		}
+	}
+
+	/// The Max Clicks Per Second cap if it's on and actually slows down the
+	/// fastest interval the current settings can click at.
+	pub fn capped_cps(&self) -> Option<f64> {
+		let fastest = match self.interval_mode {
+			IntervalMode::Constant => self.constant_interval(),
+			IntervalMode::Random => self.interval_mode_random_min as f64,
+		};
+		(self.max_cps_enabled && fastest < self.min_interval()).then(|| 1.0 / self.min_interval())
+	}
+
+	/// Checks the same ranges the settings UI enforces.
+	pub fn validate(&self) -> Result<(), String> {
+		if self.hours > 23 || self.minutes > 59 || self.seconds > 59 || self.milliseconds > 999 {
+			return Err("Click interval is out of range".to_owned());
+		}
+		if !(0.0..=MAX_RANDOM_INTERVAL).contains(&self.interval_mode_random_max)
+			|| !(0.0..=self.interval_mode_random_max).contains(&self.interval_mode_random_min)
+		{
+			return Err("Random interval is out of range".to_owned());
+		}
+		if !(0.1..=1000.0).contains(&self.max_cps) {
+			return Err("Max clicks per second is out of range".to_owned());
+		}
+		if self.post_click_delay_ms > 10_000 {
+			return Err("Post-click delay is out of range".to_owned());
+		}
+		if !(1..=10).contains(&self.anti_idle_distance) {
+			return Err("Anti-Idle distance is out of range".to_owned());
+		}
+		if !(-1000..=1000).contains(&self.cursor_offset.0) || !(-1000..=1000).contains(&self.cursor_offset.1) {
+			return Err("Cursor offset is out of range".to_owned());
+		}
+		if !(1..=1000).contains(&self.verify_cursor_timeout_ms) {
+			return Err("Verify position timeout is out of range".to_owned());
+		}
+		if self.movement_duration_ms > 1000 {
+			return Err("Movement duration is out of range".to_owned());
+		}
+		if !(1..=20).contains(&self.scroll_amount) {
+			return Err("Scroll amount is out of range".to_owned());
+		}
+		if self.click_region.2 < 1 || self.click_region.3 < 1 {
+			return Err("Click region has to be at least 1 pixel wide and tall".to_owned());
+		}
+		let (x, y, width, height) = self.click_region;
+		let coordinates = -MAX_SCREEN_COORDINATE..=MAX_SCREEN_COORDINATE;
+		if !coordinates.contains(&x)
+			|| !coordinates.contains(&y)
+			|| width > MAX_SCREEN_COORDINATE - x
+			|| height > MAX_SCREEN_COORDINATE - y
+		{
+			return Err("Click region is out of range".to_owned());
+		}
+		if !(1..=10_000).contains(&self.burst_size)
+			|| !self.burst_pause.is_finite()
+			|| !(0.0..=3600.0).contains(&self.burst_pause)
+		{
+			return Err("Burst is out of range".to_owned());
+		}
+		if self.burst_spacing_ms > 1000 {
+			return Err("Burst spacing is out of range".to_owned());
+		}
+		if self.click_steps.iter().any(|step| step.repeat == 0) {
+			return Err("Step repeat count has to be at least 1".to_owned());
+		}
+		macros::validate(&self.macro_actions)?;
+		if !(0.5..=1.0).contains(&self.template_threshold) {
+			return Err("Image match threshold is out of range".to_owned());
+		}
+		if !(1..=1000).contains(&self.key_burst_clicks) || self.key_burst_interval_ms > 1000 {
+			return Err("Click burst is out of range".to_owned());
+		}
+		if !(1..=1_000_000).contains(&self.attempt_limit) {
+			return Err("Attempt limit is out of range".to_owned());
+		}
+		if !(1..=1_000_000).contains(&self.limit_mode_clicks_amount) {
+			return Err("Click limit is out of range".to_owned());
+		}
+		if !self.limit_mode_time.is_finite() || self.limit_mode_time < 0.0 {
+			return Err("Time limit is out of range".to_owned());
+		}
+		if !(1..=1440).contains(&self.safety_cap_minutes)
+			|| !(1..=100_000_000).contains(&self.safety_cap_clicks)
+		{
+			return Err("Safety cap is out of range".to_owned());
+		}
+		let (hue, saturation, value) = self.color_mode_hsv_tolerance;
+		if hue > 180 || saturation > 100 || value > 100 {
+			return Err("Color tolerance is out of range".to_owned());
+		}
+		if self.audio_threshold > 100 {
+			return Err("Audio threshold is out of range".to_owned());
+		}
+		if self.color_mode_pause_after > 100_000 {
+			return Err("Color mode pause is out of range".to_owned());
+		}
+		if !(1..=1440).contains(&self.idle_timeout_minutes) {
+			return Err("Idle timeout is out of range".to_owned());
+		}
+		if self.window_corner_margin > 500 {
+			return Err("Window corner margin is out of range".to_owned());
+		}
+		if [self.active_hours_start, self.active_hours_end]
+			.iter()
+			.any(|&(hour, minute)| hour > 23 || minute > 59)
+		{
+			return Err("Active hours are out of range".to_owned());
+		}
+		if !(1..=100).contains(&self.battery_pause_below) {
+			return Err("Low battery threshold is out of range".to_owned());
+		}
+		if self.control_server_port < 1024 {
+			return Err("Control server port is out of range".to_owned());
+		}
+		if !(0.2..=1.0).contains(&self.window_opacity) {
+			return Err("Window opacity is out of range".to_owned());
+		}
+		if self.hold_activation_delay_ms > 2000 {
+			return Err("Hold delay is out of range".to_owned());
+		}
+		if !self.keybinds.conflicts(keybinds::SENT_KEYS).is_empty() {
+			return Err("Two actions share the same hotkey".to_owned());
+		}
+
+		Ok(())
+	}
+}
+
+/// The settings of every clicker tab, kept between runs.
+#[derive(Serialize)]
+pub struct SavedTabs {
+	pub selected: usize,
+	pub tabs: Vec<Settings>,
+}
+
+impl Default for SavedTabs {
+	fn default() -> SavedTabs {
+		SavedTabs {
+			selected: 0,
+			tabs: vec![Settings::default()],
+		}
+	}
+}
+
+/// Saved tabs as they're read back. Before every tab was saved, the file held
+/// just the selected tab's settings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredTabs {
+	Tabs { selected: usize, tabs: Vec<Value> },
+	Single(Value),
+}
+
+impl SavedTabs {
+	/// The tabs the last run was closed with, or a single tab with the defaults
+	/// if there aren't any.
+	///
+	/// When the saved file can't be used, because it's broken or from a newer
+	/// version, the defaults come with a message for the user. The file is then
+	/// moved aside the next time the tabs are saved instead of overwritten.
+	pub fn load_saved() -> (SavedTabs, Option<String>) {
+		let Some(path) = platform::config_dir().map(|dir| dir.join(SAVED_SETTINGS_FILE)) else {
+			return (SavedTabs::default(), None);
+		};
+		if !path.exists() {
+			return (SavedTabs::default(), None);
+		}
+
+		let (tabs, problem) = match SavedTabs::load(&path) {
+			Ok((tabs, warning)) => (tabs, warning.map(|warning| format!("{}, the defaults were loaded instead", warning))),
+			Err(error) => (
+				SavedTabs::default(),
+				Some(format!("Couldn't load the saved settings, the defaults were loaded instead: {}", error)),
+			),
+		};
+		let problem = problem.map(|problem| format!("{}. The old file is kept as {}", problem, SAVED_SETTINGS_BACKUP));
+
+		(tabs, problem)
+	}
+
+	/// Reads the tabs the same way `Settings::load` reads a single one. If any
+	/// tab is from a newer version, all of them are left out.
+	fn load(path: &Path) -> Result<(SavedTabs, Option<String>), String> {
+		let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+		SavedTabs::from_json(&text)
+	}
+
+	fn from_json(text: &str) -> Result<(SavedTabs, Option<String>), String> {
+		let (selected, values) = match serde_json::from_str(text).map_err(|e| e.to_string())? {
+			StoredTabs::Tabs { selected, tabs } => (selected, tabs),
+			StoredTabs::Single(value) => (0, vec![value]),
+		};
+		if values.is_empty() {
+			return Err("There are no clicker tabs".to_owned());
+		}
+
+		let mut tabs = Vec::new();
+		for value in values {
+			match Settings::from_value(value)? {
+				(settings, None) => tabs.push(settings),
+				(_, Some(warning)) => return Ok((SavedTabs::default(), Some(warning))),
+			}
+		}
+		let selected = selected.min(tabs.len() - 1);
+
+		Ok((SavedTabs { selected, tabs }, None))
+	}
+
+	/// Saves the tabs so the next run starts with them. A saved file this
+	/// version can't load is kept as `SAVED_SETTINGS_BACKUP` first.
+	pub fn save_for_next_run(&self) -> Result<(), String> {
+		let dir = platform::config_dir().ok_or("No config folder found")?;
+		fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+		let path = dir.join(SAVED_SETTINGS_FILE);
+		if path.exists() && !matches!(SavedTabs::load(&path), Ok((_, None))) {
+			fs::rename(&path, dir.join(SAVED_SETTINGS_BACKUP)).map_err(|e| e.to_string())?;
+		}
+
+		let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+		fs::write(path, text).map_err(|e| e.to_string())
+	}
+}
+
+/// Upgrades settings JSON written in format `from` to `SETTINGS_VERSION`, one
+/// version at a time. Fields added since are left out and get their defaults.
+fn migrate(value: &mut Value, from: u32) -> Result<(), String> {
+	let settings = value.as_object_mut().ok_or("Settings are not a JSON object")?;
+	if from < 1 {
+		return Err(format!("Settings version {} doesn't exist", from));
+	}
+
+	for version in from..SETTINGS_VERSION {
+		match version {
+			// Version 1 had no version field, everything else reads as is
+			1 => {}
+			_ => unreachable!("no migration from settings version {}", version),
+		}
+	}
+	settings.insert("version".to_owned(), SETTINGS_VERSION.into());
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unversioned_settings_are_upgraded() {
+		let text = r#"{
+			"interval_mode": "Random",
+			"hours": 0,
+			"minutes": 0,
+			"seconds": 2,
+			"milliseconds": 500,
+			"interval_mode_random_min": 0.5,
+			"interval_mode_random_max": 1.5,
+			"mouse_button": "Right"
+		}"#;
+
+		let (settings, warning) = Settings::from_json(text).unwrap();
+		assert_eq!(warning, None);
+		assert_eq!(settings.version, SETTINGS_VERSION);
+		assert_eq!(settings.interval_mode, IntervalMode::Random);
+		assert_eq!((settings.seconds, settings.milliseconds), (2, 500));
+		assert_eq!((settings.interval_mode_random_min, settings.interval_mode_random_max), (0.5, 1.5));
+		assert_eq!(settings.mouse_button, MouseButton::Right);
+		// Fields the file didn't have yet get their defaults
+		assert_eq!(settings.click_mode, Settings::default().click_mode);
+	}
+
+	#[test]
+	fn newer_settings_load_the_defaults_with_a_warning() {
+		let text = format!(r#"{{ "version": {}, "seconds": 5 }}"#, SETTINGS_VERSION + 1);
+
+		let (settings, warning) = Settings::from_json(&text).unwrap();
+		assert!(warning.is_some());
+		assert_eq!(settings.seconds, Settings::default().seconds);
+	}
+
+	#[test]
+	fn settings_version_zero_is_rejected() {
+		assert!(Settings::from_json(r#"{ "version": 0 }"#).is_err());
+	}
+
+	#[test]
+	fn saved_tabs_read_back_the_same() {
+		let second = Settings {
+			seconds: 3,
+			..Settings::default()
+		};
+		let saved = SavedTabs {
+			selected: 1,
+			tabs: vec![Settings::default(), second],
+		};
+
+		let text = serde_json::to_string(&saved).unwrap();
+		let (loaded, warning) = SavedTabs::from_json(&text).unwrap();
+		assert_eq!(warning, None);
+		assert_eq!(loaded.selected, 1);
+		assert_eq!(loaded.tabs.len(), 2);
+		assert_eq!(loaded.tabs[1].seconds, 3);
+	}
+
+	#[test]
+	fn a_single_saved_tab_still_loads() {
+		let (loaded, warning) = SavedTabs::from_json(r#"{ "version": 2, "seconds": 4 }"#).unwrap();
+		assert_eq!(warning, None);
+		assert_eq!(loaded.selected, 0);
+		assert_eq!(loaded.tabs.len(), 1);
+		assert_eq!(loaded.tabs[0].seconds, 4);
+	}
+
+	#[test]
+	fn a_newer_saved_tab_loads_the_defaults_with_a_warning() {
+		let text = format!(
+			r#"{{ "selected": 0, "tabs": [{{ "seconds": 4 }}, {{ "version": {}, "seconds": 5 }}] }}"#,
+			SETTINGS_VERSION + 1
+		);
+
+		let (loaded, warning) = SavedTabs::from_json(&text).unwrap();
+		assert!(warning.is_some());
+		assert_eq!(loaded.tabs.len(), 1);
+		assert_eq!(loaded.tabs[0].seconds, Settings::default().seconds);
+	}
+
+	#[test]
+	fn broken_saved_tabs_are_an_error() {
+		assert!(SavedTabs::from_json(r#"{ "selected": 0, "tabs": [] }"#).is_err());
+		assert!(SavedTabs::from_json(r#"{ "selected": 0, "tabs": [{ "version": 0 }] }"#).is_err());
+		assert!(SavedTabs::from_json("{ not json").is_err());
+	}
+}
@@ -0,0 +1,28 @@
+//! Tells the input the clickers send apart from the user's own, so the global
+//! hooks don't mistake our clicks for activity or react to them.
+
+use std::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// How long after we send input the hook events are assumed to be ours. The
+/// hook sees them a little after they're sent, and later still under load.
+const GRACE: Duration = Duration::from_millis(150);
+
+/// Shared by every clicker, one clicker's clicks aren't user input to another.
+static LAST_SYNTHETIC_INPUT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Marks input as being sent right now. Call it both before and after sending,
+/// so slow input like a smart double click stays covered until it's done.
+pub fn mark() {
+	*LAST_SYNTHETIC_INPUT.lock().unwrap() = Some(Instant::now());
+}
+
+/// Whether an input event seen right now was likely sent by a clicker.
+pub fn is_recent() -> bool {
+	LAST_SYNTHETIC_INPUT
+		.lock()
+		.unwrap()
+		.is_some_and(|last| last.elapsed() <= GRACE)
+}
@@ -0,0 +1,195 @@
+//! Finding a small template image on screen, so the clicker can click
+//! wherever it shows up instead of at a set position.
+
+use std::path::Path;
+
+use image::{imageops, GrayImage};
+
+/// How much smaller the screen is searched first, before only the best spots
+/// are compared at full size. Searching every position at full size would
+/// take far longer than most intervals.
+const COARSE_SCALE: u32 = 4;
+/// Shrinking blurs both images, so the coarse search is a little less strict.
+const COARSE_SLACK: f32 = 0.05;
+/// How many of the best coarse spots are compared at full size.
+const MAX_CANDIDATES: usize = 16;
+
+/// Reads the template as grayscale, colors only matter as far as they differ in brightness.
+pub fn load(path: &Path) -> Result<GrayImage, String> {
+	let template = image::open(path).map_err(|e| e.to_string())?.into_luma8();
+	if template.width() == 0 || template.height() == 0 {
+		return Err("The image is empty".to_owned());
+	}
+
+	Ok(template)
+}
+
+/// A grayscale screenshot of the main screen, in pixels.
+pub fn capture_screen() -> Option<GrayImage> {
+	let bitmap = autopilot::bitmap::capture_screen().ok()?;
+	// autopilot uses an older version of image, so the pixels are moved over as they are
+	let rgba = bitmap.image.to_rgba();
+	let (width, height) = rgba.dimensions();
+	let rgba = image::RgbaImage::from_raw(width, height, rgba.into_raw())?;
+
+	Some(image::DynamicImage::ImageRgba8(rgba).into_luma8())
+}
+
+/// The center of the spot on `screen` that looks the most like `template`, if
+/// it's at least `threshold` alike, where 1 is identical.
+pub fn find(screen: &GrayImage, template: &GrayImage, threshold: f32) -> Option<(u32, u32)> {
+	let (width, height) = template.dimensions();
+	if width > screen.width() || height > screen.height() {
+		return None;
+	}
+
+	// Small templates have too little left to compare once shrunk
+	let scale = COARSE_SCALE.min(width / 4).min(height / 4).max(1);
+	let candidates: Vec<(u32, u32)> = if scale == 1 {
+		positions(screen, template).collect()
+	} else {
+		let small_screen = imageops::resize(
+			screen,
+			screen.width() / scale,
+			screen.height() / scale,
+			imageops::FilterType::Triangle,
+		);
+		let small_template = imageops::resize(template, width / scale, height / scale, imageops::FilterType::Triangle);
+		let budget = budget(&small_template, threshold - COARSE_SLACK);
+
+		let mut matches: Vec<(u64, u32, u32)> = positions(&small_screen, &small_template)
+			.filter_map(|(x, y)| Some((difference(&small_screen, &small_template, x, y, budget)?, x, y)))
+			.collect();
+		matches.sort_unstable();
+		matches.truncate(MAX_CANDIDATES);
+
+		// Every full size position the coarse spots could have come from
+		matches
+			.into_iter()
+			.flat_map(|(_, x, y)| {
+				let xs = (x * scale).saturating_sub(scale)..=((x + 1) * scale).min(screen.width() - width);
+				let ys = (y * scale).saturating_sub(scale)..=((y + 1) * scale).min(screen.height() - height);
+				ys.flat_map(move |y| xs.clone().map(move |x| (x, y)))
+			})
+			.collect()
+	};
+
+	let mut budget = budget(template, threshold);
+	let mut best = None;
+	for (x, y) in candidates {
+		if let Some(difference) = difference(screen, template, x, y, budget) {
+			// Only a closer match can beat this one now
+			budget = difference;
+			best = Some((x + width / 2, y + height / 2));
+			if difference == 0 {
+				break;
+			}
+		}
+	}
+
+	best
+}
+
+/// Every position the template fits at on the screen.
+fn positions(screen: &GrayImage, template: &GrayImage) -> impl Iterator<Item = (u32, u32)> {
+	let (max_x, max_y) = (screen.width() - template.width(), screen.height() - template.height());
+	(0..=max_y).flat_map(move |y| (0..=max_x).map(move |x| (x, y)))
+}
+
+/// The most the template's pixels can differ from the screen in total and still be `threshold` alike.
+fn budget(template: &GrayImage, threshold: f32) -> u64 {
+	let pixels = template.width() as f32 * template.height() as f32;
+	((1.0 - threshold.clamp(0.0, 1.0)) * 255.0 * pixels) as u64
+}
+
+/// How much the template differs from the screen with its top left corner at
+/// `x`, `y`, or `None` as soon as it differs more than `budget`.
+fn difference(screen: &GrayImage, template: &GrayImage, x: u32, y: u32, budget: u64) -> Option<u64> {
+	let (width, screen_width) = (template.width() as usize, screen.width() as usize);
+	let (screen_pixels, template_pixels) = (screen.as_raw(), template.as_raw());
+
+	let mut total = 0;
+	for row in 0..template.height() as usize {
+		let start = (y as usize + row) * screen_width + x as usize;
+		total += screen_pixels[start..start + width]
+			.iter()
+			.zip(&template_pixels[row * width..(row + 1) * width])
+			.map(|(a, b)| a.abs_diff(*b) as u64)
+			.sum::<u64>();
+		if total > budget {
+			return None;
+		}
+	}
+
+	Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Blurred noise, so every spot looks different but neighbors are alike
+	/// like on a real screen.
+	fn screen(width: u32, height: u32) -> GrayImage {
+		let noise = GrayImage::from_fn(width, height, |x, y| {
+			let hash = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663)).wrapping_mul(2_654_435_761);
+			image::Luma([(hash >> 24) as u8])
+		});
+		imageops::blur(&noise, 1.5)
+	}
+
+	fn crop(screen: &GrayImage, x: u32, y: u32, width: u32, height: u32) -> GrayImage {
+		imageops::crop_imm(screen, x, y, width, height).to_image()
+	}
+
+	#[test]
+	fn finds_a_template_at_its_center() {
+		let screen = screen(200, 150);
+		let template = crop(&screen, 53, 37, 24, 20);
+		assert_eq!(find(&screen, &template, 0.95), Some((53 + 12, 37 + 10)));
+	}
+
+	#[test]
+	fn finds_a_template_at_the_right_and_bottom_edges() {
+		let screen = screen(201, 151);
+		let template = crop(&screen, 201 - 24, 151 - 20, 24, 20);
+		assert_eq!(find(&screen, &template, 0.95), Some((201 - 12, 151 - 10)));
+
+		let template = crop(&screen, 201 - 24, 10, 24, 20);
+		assert_eq!(find(&screen, &template, 0.95), Some((201 - 12, 20)));
+		let template = crop(&screen, 10, 151 - 20, 24, 20);
+		assert_eq!(find(&screen, &template, 0.95), Some((22, 151 - 10)));
+	}
+
+	#[test]
+	fn finds_a_small_template_without_shrinking() {
+		let screen = screen(60, 40);
+		let template = crop(&screen, 41, 17, 6, 5);
+		assert_eq!(find(&screen, &template, 0.95), Some((41 + 3, 17 + 2)));
+	}
+
+	#[test]
+	fn a_template_the_size_of_the_screen_is_the_whole_screen() {
+		let screen = screen(64, 48);
+		assert_eq!(find(&screen, &screen.clone(), 1.0), Some((32, 24)));
+	}
+
+	#[test]
+	fn a_template_larger_than_the_screen_is_never_found() {
+		let screen = screen(64, 48);
+		assert_eq!(find(&screen, &GrayImage::new(65, 48), 0.0), None);
+		assert_eq!(find(&screen, &GrayImage::new(64, 49), 0.0), None);
+	}
+
+	#[test]
+	fn a_threshold_of_one_needs_an_exact_match() {
+		let screen = screen(200, 150);
+		let mut template = crop(&screen, 53, 37, 24, 20);
+		assert_eq!(find(&screen, &template, 1.0), Some((53 + 12, 37 + 10)));
+
+		let pixel = template.get_pixel_mut(5, 5);
+		pixel.0[0] = pixel.0[0].wrapping_add(128);
+		assert_eq!(find(&screen, &template, 1.0), None);
+		assert_eq!(find(&screen, &template, 0.99), Some((53 + 12, 37 + 10)));
+	}
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Align2, Color32, Margin, RichText, Rounding};
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+const MAX_TOASTS: usize = 5;
+
+/// Matches the colors of the beta, warning and danger tags.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ToastLevel {
+	Info,
+	Warning,
+	Danger,
+}
+
+impl ToastLevel {
+	fn color(&self) -> Color32 {
+		match self {
+			ToastLevel::Info => Color32::from_rgb(0, 170, 255),
+			ToastLevel::Warning => Color32::from_rgb(230, 140, 0),
+			ToastLevel::Danger => Color32::from_rgb(255, 0, 0),
+		}
+	}
+}
+
+struct Toast {
+	text: String,
+	level: ToastLevel,
+	created: Instant,
+}
+
+/// A queue of short, non-blocking messages shown stacked in the bottom right
+/// corner until they expire.
+#[derive(Default)]
+pub struct Toasts {
+	toasts: Vec<Toast>,
+}
+
+impl Toasts {
+	pub fn push(&mut self, level: ToastLevel, text: impl Into<String>) {
+		if self.toasts.len() == MAX_TOASTS {
+			self.toasts.remove(0);
+		}
+
+		self.toasts.push(Toast {
+			text: text.into(),
+			level,
+			created: Instant::now(),
+		});
+	}
+
+	pub fn show(&mut self, ctx: &egui::Context) {
+		self.toasts
+			.retain(|toast| toast.created.elapsed() < TOAST_DURATION);
+		if self.toasts.is_empty() {
+			return;
+		}
+
+		egui::Area::new(egui::Id::new("toasts"))
+			.anchor(Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+			.order(egui::Order::Tooltip)
+			.interactable(false)
+			.show(ctx, |ui| {
+				for toast in &self.toasts {
+					egui::Frame::default()
+						.fill(toast.level.color())
+						.inner_margin(Margin::symmetric(8.0, 4.0))
+						.rounding(Rounding::same(3.0))
+						.show(ui, |ui| {
+							ui.set_max_width(250.0);
+							ui.label(RichText::new(&toast.text).color(Color32::WHITE));
+						});
+					ui.add_space(4.0);
+				}
+			});
+	}
+}
@@ -0,0 +1,40 @@
+//! The one-time "what's new" popup shown on the first run after an update.
+
+use std::fs;
+
+use crate::platform;
+
+/// Every version's changes, newest first, each under a `## <version>` heading.
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/// The changes listed under this version in the changelog.
+pub fn changes() -> impl Iterator<Item = &'static str> {
+	let heading = format!("## {}", env!("CARGO_PKG_VERSION"));
+	CHANGELOG
+		.lines()
+		.skip_while(move |line| line.trim() != heading)
+		.skip(1)
+		.take_while(|line| !line.starts_with("## "))
+		.filter_map(|line| line.strip_prefix("- "))
+}
+
+/// Remembers the version this run is from and tells whether it's newer than
+/// the one the last run was from. A first install has nothing new to show.
+pub fn check() -> bool {
+	let Some(dir) = platform::config_dir() else {
+		return false;
+	};
+	let path = dir.join("last_version");
+	let current = env!("CARGO_PKG_VERSION");
+	let last = fs::read_to_string(&path).ok();
+	if last.as_deref().map(str::trim) == Some(current) {
+		return false;
+	}
+
+	// Recorded right away, so the popup doesn't come back if it's never closed
+	if fs::create_dir_all(&dir).is_err() || fs::write(&path, current).is_err() {
+		return false;
+	}
+
+	last.is_some()
+}